@@ -0,0 +1,119 @@
+//! Day 3's column lookups against the three-line scan they replaced, on a
+//! generated 10k×10k schematic.
+//!
+//! cargo bench -p aoc-2023 --bench day3
+
+use aoc_2023::day3::schematic::{
+    generate, Neighbourhood, Number, Schematic, SchematicLine, Symbol,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+/// How many rows the symbols come from. Scanning every row of the grid
+/// takes minutes, and both sides do the same work per row.
+const ROWS: usize = 100;
+
+/// The old rule: a symbol touches a number if it's over one of its digits
+/// or in the column either side.
+fn is_adjacent(sym: &Symbol, num: &Number) -> bool {
+    let in_range = num.pos.contains(&sym.pos);
+    let adjacent_to_start = sym.pos <= num.pos.start && num.pos.start - sym.pos <= 1;
+    let adjacent_to_end = sym.pos == num.pos.end;
+
+    in_range || adjacent_to_start || adjacent_to_end
+}
+
+/// Gear ratios found the old way, by checking every number on a gear's
+/// line and the lines either side of it.
+fn scan_gears(lines: &[SchematicLine]) -> Vec<(u32, u32)> {
+    let mut gears = Vec::new();
+    for (row, line) in lines.iter().enumerate().take(ROWS) {
+        let nearby = &lines[row.saturating_sub(1)..(row + 2).min(lines.len())];
+        for sym in line.symbols.iter().filter(|sym| sym.ch == '*') {
+            let touching = nearby
+                .iter()
+                .flat_map(|line| &line.numbers)
+                .filter(|num| is_adjacent(sym, num))
+                .collect::<Vec<_>>();
+            if let [first, second] = touching[..] {
+                gears.push((first.value, second.value));
+            }
+        }
+    }
+    gears
+}
+
+/// The same gear ratios found through [`Schematic::numbers_near`].
+fn lookup_gears(schematic: &Schematic) -> Vec<(u32, u32)> {
+    let hood = Neighbourhood::default();
+    let mut gears = Vec::new();
+    for (row, line) in schematic.lines.iter().enumerate().take(ROWS) {
+        for sym in line.symbols.iter().filter(|sym| sym.ch == '*') {
+            if let [first, second] = schematic.numbers_near(row, sym, hood)[..] {
+                gears.push((first.value, second.value));
+            }
+        }
+    }
+    gears
+}
+
+/// Part numbers found the old way, by checking every symbol on a number's
+/// line and the lines either side of it.
+fn scan_parts(lines: &[SchematicLine]) -> Vec<u32> {
+    let mut parts = Vec::new();
+    for (row, line) in lines.iter().enumerate().take(ROWS) {
+        let nearby = &lines[row.saturating_sub(1)..(row + 2).min(lines.len())];
+        for num in &line.numbers {
+            let touched = nearby
+                .iter()
+                .flat_map(|line| &line.symbols)
+                .any(|sym| is_adjacent(sym, num));
+            if touched {
+                parts.push(num.value);
+            }
+        }
+    }
+    parts
+}
+
+/// The same part numbers found through [`Schematic::symbols_near`].
+fn lookup_parts(schematic: &Schematic) -> Vec<u32> {
+    let hood = Neighbourhood::default();
+    let mut parts = Vec::new();
+    for (row, line) in schematic.lines.iter().enumerate().take(ROWS) {
+        for num in &line.numbers {
+            if !schematic.symbols_near(row, num, hood).is_empty() {
+                parts.push(num.value);
+            }
+        }
+    }
+    parts
+}
+
+fn large_schematic(c: &mut Criterion) {
+    let schematic = generate(10_000, 10_000).parse::<Schematic>().unwrap();
+
+    // Only what's found is checked here. How long it takes is for the
+    // report to show, not to pass or fail on.
+    assert_eq!(scan_gears(&schematic.lines), lookup_gears(&schematic));
+    assert_eq!(scan_parts(&schematic.lines), lookup_parts(&schematic));
+
+    let mut group = c.benchmark_group("10k×10k schematic");
+    group.sample_size(10);
+    group.bench_function("gears/three-line scan", |b| {
+        b.iter(|| scan_gears(black_box(&schematic.lines)))
+    });
+    group.bench_function("gears/column lookup", |b| {
+        b.iter(|| lookup_gears(black_box(&schematic)))
+    });
+    group.bench_function("parts/three-line scan", |b| {
+        b.iter(|| scan_parts(black_box(&schematic.lines)))
+    });
+    group.bench_function("parts/column lookup", |b| {
+        b.iter(|| lookup_parts(black_box(&schematic)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, large_schematic);
+criterion_main!(benches);
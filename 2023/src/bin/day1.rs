@@ -0,0 +1,12 @@
+use aoc_2023::{
+    day1::{PartOne, PartTwo},
+    normalise, show_solutions,
+};
+
+fn main() {
+    let input = &normalise::input(include_str!("../../puzzle-input/day1.txt"));
+    let part1 = PartOne::new(input);
+    let part2 = PartTwo::new(input);
+
+    show_solutions(part1, part2);
+}
@@ -0,0 +1,47 @@
+use aoc_2023::{
+    day2::{self, stats, PartOne, PartTwo, BAG},
+    normalise, show_notes, show_solutions,
+};
+use std::{env, process::ExitCode};
+
+const USAGE: &str = "usage: day2 [report [<bucket width>]]";
+
+fn main() -> ExitCode {
+    let input = &normalise::input(include_str!("../../puzzle-input/day2.txt"));
+
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["report"] => report(input, 500),
+        ["report", width] => match width.parse() {
+            Ok(width) => report(input, width),
+            Err(_) => usage(),
+        },
+        [] => {
+            let part1 = PartOne::new(input);
+            let part2 = PartTwo::new(input);
+            show_solutions(part1, part2);
+            ExitCode::SUCCESS
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn report(input: &str, width: u32) -> ExitCode {
+    let games = day2::parse(input).unwrap();
+    match stats::report(&games, BAG, width) {
+        Ok(report) => {
+            print!("{report}");
+            show_notes();
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("day2 report: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
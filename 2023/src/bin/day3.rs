@@ -0,0 +1,71 @@
+use aoc_2023::{
+    arith::Wide,
+    day3::{
+        around,
+        render::Render,
+        schematic::{Arity, Neighbourhood},
+        PartOne, PartTwo,
+    },
+    normalise, show_solutions,
+};
+use std::{env, fs, process::ExitCode};
+
+const USAGE: &str = "usage: day3 [--render | --svg <path> | --html <path> | --around <symbols> <n | n+> [<radius> <chebyshev | manhattan>]]";
+
+fn main() -> ExitCode {
+    let input = &normalise::input(include_str!("../../puzzle-input/day3.txt"));
+
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["--render"] => {
+            print!("{}", Render::new(input).ansi());
+            ExitCode::SUCCESS
+        }
+        ["--svg", path] => write(path, Render::new(input).svg()),
+        ["--html", path] => write(path, Render::new(input).html()),
+        ["--around", symbols, arity] => match arity.parse::<Arity>() {
+            Ok(arity) => {
+                show_around(around(input, symbols, arity, Neighbourhood::default()));
+                ExitCode::SUCCESS
+            }
+            Err(_) => usage(),
+        },
+        ["--around", symbols, arity, radius, metric] => {
+            match (arity.parse::<Arity>(), radius.parse(), metric.parse()) {
+                (Ok(arity), Ok(radius), Ok(metric)) => {
+                    let hood = Neighbourhood { radius, metric };
+                    show_around(around(input, symbols, arity, hood));
+                    ExitCode::SUCCESS
+                }
+                _ => usage(),
+            }
+        }
+        [] => {
+            let part1 = PartOne::new(input);
+            let part2 = PartTwo::new(input);
+            show_solutions(part1, part2);
+            ExitCode::SUCCESS
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn write(path: &str, contents: String) -> ExitCode {
+    match fs::write(path, contents) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("day3: couldn't write {path}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn show_around((sum, product): (Wide<u32>, Wide<u32>)) {
+    println!("Sum: {sum}");
+    println!("Sum of products: {product}");
+}
@@ -0,0 +1,21 @@
+use aoc_2023::{
+    day5::{trace_seed_ranges, PartOne, PartTwo},
+    normalise, show_solutions,
+};
+use std::{env, fs};
+
+fn main() {
+    let input = &normalise::input(include_str!("../../puzzle-input/day5.txt"));
+
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["--table"] => print!("{}", trace_seed_ranges(input).table()),
+        ["--dot", path] => fs::write(path, trace_seed_ranges(input).dot()).unwrap(),
+        [] => {
+            let part1 = PartOne::new(input);
+            let part2 = PartTwo::new(input);
+            show_solutions(part1, part2);
+        }
+        _ => eprintln!("usage: day5 [--table | --dot <path>]"),
+    }
+}
@@ -0,0 +1,67 @@
+use aoc_2023::{
+    day6::{
+        race::{Physics, Race},
+        PartOne, PartTwo,
+    },
+    normalise, show_solutions,
+};
+use std::{env, process::ExitCode};
+
+const USAGE: &str = "usage: day6 [explore <time> <record> [<acceleration> [<max speed>]]]";
+
+fn main() -> ExitCode {
+    let input = &normalise::input(include_str!("../../puzzle-input/day6.txt"));
+
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["explore", time, record, ref physics @ ..] if physics.len() <= 2 => {
+            match race(time, record, physics) {
+                Some(race) => {
+                    explore(&race);
+                    ExitCode::SUCCESS
+                }
+                None => usage(),
+            }
+        }
+        [] => {
+            let part1 = PartOne::new(input);
+            let part2 = PartTwo::new(input);
+            show_solutions(part1, part2);
+            ExitCode::SUCCESS
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+/// The race described by `explore`'s arguments, if they're all numbers.
+fn race(time: &str, record: &str, physics: &[&str]) -> Option<Race> {
+    let physics = Physics {
+        acceleration: physics.first().map_or(Ok(1), |a| a.parse()).ok()?,
+        max_speed: physics.get(1).map(|max| max.parse()).transpose().ok()?,
+    };
+    let race = Race::from((time.parse().ok()?, record.parse().ok()?));
+    Some(race.with_physics(physics))
+}
+
+fn explore(race: &Race) {
+    let (hold_time, distance) = race.optimum();
+    println!("Best hold: {hold_time} ms for {distance} mm");
+
+    match race.winning_holds() {
+        Some(holds) => println!(
+            "Winning holds: {} to {} ms ({} ways)",
+            holds.start(),
+            holds.end(),
+            race.num_ways_to_win()
+        ),
+        None => println!("Winning holds: none"),
+    }
+
+    println!();
+    print!("{}", race.chart(60, 16));
+}
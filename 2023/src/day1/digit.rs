@@ -41,7 +41,7 @@ impl Iterator for DigitParser<'_> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Digit {
     One,
     Two,
@@ -0,0 +1,114 @@
+pub mod digit;
+
+use crate::{
+    aoc,
+    arith::{self, Wide, Widen},
+    iter::IteratorExt,
+    parsing,
+};
+use digit::{Digit, DigitParser};
+
+/// The digits on each line, whether written as digits or spelled out.
+pub fn parse(input: &str) -> Result<Vec<Vec<Digit>>, parsing::BadLine> {
+    parsing::lines(input, |line| digits(DigitParser::new(line)))
+}
+
+/// The digits on each line, ignoring any that are spelled out.
+pub fn parse_numerals(input: &str) -> Result<Vec<Vec<Digit>>, parsing::BadLine> {
+    parsing::lines(input, |line| {
+        digits(line.chars().filter_map(|ch| ch.to_string().parse().ok()))
+    })
+}
+
+fn digits(found: impl Iterator<Item = Digit>) -> Result<Vec<Digit>, &'static str> {
+    let digits = found.collect::<Vec<_>>();
+    match digits.is_empty() {
+        true => Err("no digits on line"),
+        false => Ok(digits),
+    }
+}
+
+fn calibration_sum(lines: &[Vec<Digit>]) -> Wide<u32> {
+    let values = lines.iter().filter_map(|digits| {
+        let (first, last) = digits.iter().first_and_last()?;
+
+        format!("{}{}", first, last).parse::<u32>().ok()
+    });
+    arith::sum("calibration sum", values.map(Widen::widen))
+}
+
+/*
+* Part One
+*/
+#[aoc(day = 1, part = 1, parse = parse_numerals)]
+pub fn part1(lines: &[Vec<Digit>]) -> Wide<u32> {
+    calibration_sum(lines)
+}
+
+/*
+* Part Two
+*/
+#[aoc(day = 1, part = 2)]
+pub fn part2(lines: &[Vec<Digit>]) -> Wide<u32> {
+    calibration_sum(lines)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Solution;
+
+    #[test]
+    fn test_sample_input_part_1() {
+        let input = "
+            1abc2
+            pqr3stu8vwx
+            a1b2c3d4e5f
+            treb7uchet
+        "
+        .trim();
+
+        let expected = 142;
+        let solution = PartOne::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sample_input_part2() {
+        let input = "
+            two1nine
+            eightwothree
+            abcone2threexyz
+            xtwone3four
+            4nineeightseven2
+            zoneight234
+            7pqrstsixteen
+        "
+        .trim();
+
+        let expected = 281;
+        let solution = PartTwo::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_lines_without_digits() {
+        let input = "1abc2\nno digits\nthree";
+
+        parsing::set_mode(parsing::Mode::Lenient);
+        parsing::take_warnings();
+        assert_eq!(PartOne::new(input).solve(), 12);
+        assert_eq!(PartTwo::new(input).solve(), 45);
+        let skipped = parsing::take_warnings();
+        assert_eq!(
+            skipped.iter().map(|bad| bad.line).collect::<Vec<_>>(),
+            [2, 3]
+        );
+
+        parsing::set_mode(parsing::Mode::Strict);
+        let err = parse_numerals(input).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: no digits on line: `no digits`");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::arith;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -70,6 +71,35 @@ impl FromStr for Game {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Colour {
+    pub const ALL: [Colour; 3] = [Colour::Red, Colour::Green, Colour::Blue];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colour::Red => "red",
+            Colour::Green => "green",
+            Colour::Blue => "blue",
+        }
+    }
+
+    /// The same bag with `extra` more cubes of this colour.
+    pub fn add(&self, bag: (u32, u32, u32), extra: u32) -> (u32, u32, u32) {
+        let (red, green, blue) = bag;
+        match self {
+            Colour::Red => (arith::add("red cubes", red, extra), green, blue),
+            Colour::Green => (red, arith::add("green cubes", green, extra), blue),
+            Colour::Blue => (red, green, arith::add("blue cubes", blue, extra)),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Reveal {
     red: Option<u32>,
@@ -77,15 +107,30 @@ pub struct Reveal {
     blue: Option<u32>,
 }
 
+impl Reveal {
+    /// How many cubes of `colour` were shown, with a colour that isn't
+    /// mentioned counting as none.
+    pub fn count(&self, colour: Colour) -> u32 {
+        let count = match colour {
+            Colour::Red => self.red,
+            Colour::Green => self.green,
+            Colour::Blue => self.blue,
+        };
+        count.unwrap_or(0)
+    }
+}
+
 impl FromStr for Reveal {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (red, green, blue) = s.split(',').try_fold((None, None, None), |mut colors, c| {
-            match c.trim().split_once(' ') {
-                Some((num, "red")) => colors.0 = num.parse().ok(),
-                Some((num, "green")) => colors.1 = num.parse().ok(),
-                Some((num, "blue")) => colors.2 = num.parse().ok(),
+            let (num, colour) = c.trim().split_once(' ').ok_or("unable to parse colors")?;
+            let count = Some(num.parse().map_err(|_| "invalid cube count")?);
+            match colour {
+                "red" => colors.0 = count,
+                "green" => colors.1 = count,
+                "blue" => colors.2 = count,
                 _ => return Err("unable to parse colors"),
             };
             Ok(colors)
@@ -127,4 +172,12 @@ mod test {
         let game = input.parse::<Game>().unwrap();
         assert_eq!(game, expected);
     }
+
+    #[test]
+    fn test_bad_count_is_an_error() {
+        assert_eq!(
+            "Game 1: x red, 2 blue".parse::<Game>(),
+            Err("invalid cube count")
+        );
+    }
 }
@@ -0,0 +1,102 @@
+pub mod game;
+pub mod stats;
+
+use crate::{
+    aoc,
+    arith::{self, Wide, Widen},
+    parsing,
+};
+use game::Game;
+
+/// The bag part one checks every game against: 12 red, 13 green and 14
+/// blue cubes.
+pub const BAG: (u32, u32, u32) = (12, 13, 14);
+
+pub fn parse(input: &str) -> Result<Vec<Game>, parsing::BadLine> {
+    parsing::lines(input, str::parse)
+}
+
+/*
+* Part One
+*/
+#[aoc(day = 2, part = 1)]
+pub fn part1(games: &[Game]) -> Wide<u32> {
+    let ids = games
+        .iter()
+        .filter(|game| game.is_possible(&BAG))
+        .map(|game| game.id.widen());
+    arith::sum("sum of possible game ids", ids)
+}
+
+/*
+* Part Two
+*/
+#[aoc(day = 2, part = 2)]
+pub fn part2(games: &[Game]) -> Wide<u32> {
+    let powers = games.iter().map(|game| power(game.min_cubes_required()));
+    arith::sum("sum of game powers", powers)
+}
+
+/// A bag's red, green and blue cube counts multiplied together.
+pub fn power((red, green, blue): (u32, u32, u32)) -> Wide<u32> {
+    arith::product("bag power", [red, green, blue].map(Widen::widen))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Solution;
+
+    #[test]
+    fn test_sample_input_part1() {
+        let input = "
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+        ";
+
+        let expected = 8;
+        let solution = PartOne::new(input).solve();
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sample_input_part2() {
+        let input = "
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+        ";
+
+        let expected = 2286;
+        let solution = PartTwo::new(input).solve();
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_unparsable_games() {
+        let input = "
+            Game 1: 3 blue, 4 red
+            Game two: 1 blue
+            Game 3: 20 red
+        ";
+
+        parsing::set_mode(parsing::Mode::Lenient);
+        parsing::take_warnings();
+        assert_eq!(PartOne::new(input).solve(), 1);
+        let skipped = parsing::take_warnings();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, 3);
+
+        parsing::set_mode(parsing::Mode::Strict);
+        let err = parse(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3: unable to parse game number: `Game two: 1 blue`"
+        );
+    }
+}
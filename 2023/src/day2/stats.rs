@@ -0,0 +1,232 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use super::{
+    game::{Colour, Game},
+    power,
+};
+use crate::arith::{self, Int, Wide, Widen};
+
+/// The most cubes of one colour shown at once, and how many were shown on
+/// average, across every reveal of every game.
+#[derive(Debug, PartialEq)]
+pub struct ColourStats {
+    pub max: u32,
+    pub mean: f64,
+}
+
+pub fn colour_stats(games: &[Game], colour: Colour) -> ColourStats {
+    let counts = games
+        .iter()
+        .flat_map(|game| game.reveals.iter())
+        .map(|reveal| reveal.count(colour))
+        .collect::<Vec<_>>();
+
+    let max = counts.iter().copied().max().unwrap_or(0);
+    let mean = match counts.len() {
+        0 => 0.0,
+        len => counts.iter().sum::<u32>() as f64 / len as f64,
+    };
+
+    ColourStats { max, mean }
+}
+
+/// How many games have a minimum bag power in each `width` wide bucket,
+/// keyed by where the bucket starts.
+pub fn power_distribution(
+    games: &[Game],
+    width: u32,
+) -> Result<BTreeMap<Wide<u32>, usize>, &'static str> {
+    if width == 0 {
+        return Err("bucket width must be at least 1");
+    }
+    let width = width.widen();
+
+    Ok(games
+        .iter()
+        .map(|game| power(game.min_cubes_required()))
+        .fold(BTreeMap::new(), |mut buckets, power| {
+            *buckets.entry(round_down(power, &width)).or_default() += 1;
+            buckets
+        }))
+}
+
+fn round_down<T: Int>(value: T, step: &T) -> T {
+    value.clone() - value % step.clone()
+}
+
+/// The ids of games that `bag` rules out but one more cube of `colour`
+/// would allow.
+pub fn possible_with_one_more(games: &[Game], bag: (u32, u32, u32), colour: Colour) -> Vec<u32> {
+    let bigger = colour.add(bag, 1);
+
+    games
+        .iter()
+        .filter(|game| !game.is_possible(&bag) && game.is_possible(&bigger))
+        .map(|game| game.id)
+        .collect()
+}
+
+/// The smallest bag every game is possible with.
+pub fn minimum_bag(games: &[Game]) -> (u32, u32, u32) {
+    games
+        .iter()
+        .map(Game::min_cubes_required)
+        .fold((0, 0, 0), |bag, cubes| {
+            (bag.0.max(cubes.0), bag.1.max(cubes.1), bag.2.max(cubes.2))
+        })
+}
+
+/// Everything above as plain text, checked against `bag`, with powers
+/// bucketed `width` at a time.
+pub fn report(games: &[Game], bag: (u32, u32, u32), width: u32) -> Result<String, &'static str> {
+    let mut out = String::new();
+    let describe =
+        |(red, green, blue): (u32, u32, u32)| format!("{red} red, {green} green, {blue} blue");
+
+    writeln!(out, "Games: {}", games.len()).unwrap();
+    writeln!(
+        out,
+        "Reveals: {}",
+        games.iter().map(|game| game.reveals.len()).sum::<usize>()
+    )
+    .unwrap();
+
+    writeln!(out, "\nCubes per reveal:").unwrap();
+    for colour in Colour::ALL {
+        let stats = colour_stats(games, colour);
+        writeln!(
+            out,
+            "  {:<5}  max {:>3}  mean {:>6.2}",
+            colour.name(),
+            stats.max,
+            stats.mean
+        )
+        .unwrap();
+    }
+
+    let minimum = minimum_bag(games);
+    writeln!(out, "\nMinimum bag for every game: {}", describe(minimum)).unwrap();
+    writeln!(out, "Power: {}", power(minimum)).unwrap();
+
+    writeln!(out, "\nOne more cube than {}:", describe(bag)).unwrap();
+    for colour in Colour::ALL {
+        let ids = possible_with_one_more(games, bag, colour);
+        let ids = match ids.is_empty() {
+            true => "no games".to_string(),
+            false => format!(
+                "games {}",
+                ids.iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        writeln!(out, "  {:<5}  {ids}", colour.name()).unwrap();
+    }
+
+    let distribution = power_distribution(games, width)?;
+    let widest = distribution.values().copied().max().unwrap_or(0);
+    writeln!(out, "\nMinimum bag powers:").unwrap();
+    for (start, count) in distribution {
+        let bar = "#".repeat((count * 40).div_ceil(widest.max(1)));
+        let from = start.to_string();
+        let end = arith::add("bucket end", start, width.widen());
+        writeln!(out, "  {from:>5}..{end:<5}  {bar} {count}").unwrap();
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "
+        Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+    ";
+
+    fn games() -> Vec<Game> {
+        INPUT
+            .trim()
+            .lines()
+            .map(|line| line.trim().parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_colour_stats() {
+        let games = games();
+
+        let red = colour_stats(&games, Colour::Red);
+        assert_eq!(red.max, 20);
+        assert_eq!(red.mean, 61.0 / 14.0);
+
+        assert_eq!(colour_stats(&games, Colour::Blue).max, 15);
+        assert_eq!(colour_stats(&[], Colour::Green).mean, 0.0);
+    }
+
+    #[test]
+    fn test_minimum_bag_and_powers() {
+        let games = games();
+
+        assert_eq!(minimum_bag(&games), (20, 13, 15));
+        assert!(games
+            .iter()
+            .all(|game| game.is_possible(&minimum_bag(&games))));
+
+        // Powers are 48, 12, 1560, 630 and 36.
+        let buckets = power_distribution(&games, 500)
+            .unwrap()
+            .into_iter()
+            .map(|(start, count)| (start.to_string(), count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            buckets,
+            [
+                ("0".to_string(), 3),
+                ("500".to_string(), 1),
+                ("1500".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_possible_with_one_more() {
+        let games = games();
+
+        assert_eq!(
+            possible_with_one_more(&games, (13, 13, 15), Colour::Red),
+            vec![4]
+        );
+        assert_eq!(
+            possible_with_one_more(&games, (12, 13, 14), Colour::Red),
+            vec![]
+        );
+        assert_eq!(
+            possible_with_one_more(&games, (14, 13, 14), Colour::Blue),
+            vec![4]
+        );
+        assert_eq!(
+            possible_with_one_more(&games, (20, 12, 15), Colour::Green),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn test_report_bucket_width() {
+        let games = games();
+
+        let text = report(&games, (12, 13, 14), 1000).unwrap();
+        assert!(text.contains("      0..1000   ######################################## 4\n"));
+        assert!(text.contains("   1000..2000   ########## 1\n"));
+
+        assert_eq!(
+            report(&games, (12, 13, 14), 0),
+            Err("bucket width must be at least 1")
+        );
+    }
+}
@@ -0,0 +1,134 @@
+pub mod render;
+pub mod schematic;
+
+use crate::{
+    aoc,
+    arith::{self, Wide, Widen},
+};
+use schematic::{Arity, Gear, Neighbourhood, Schematic};
+
+/// Every character is a digit, a dot or a symbol, so reading a schematic
+/// can't fail.
+pub fn parse(input: &str) -> Schematic {
+    let Ok(schematic) = input.parse();
+    schematic
+}
+
+/// Totals the numbers within `hood` of every symbol in `symbols` that
+/// reaches `arity` numbers, both added up and multiplied together per
+/// symbol. Part two is
+/// `around(input, "*", Arity::Exactly(2), Neighbourhood::default()).1`.
+pub fn around(
+    input: &str,
+    symbols: &str,
+    arity: Arity,
+    hood: Neighbourhood,
+) -> (Wide<u32>, Wide<u32>) {
+    let schematic = parse(input);
+    let adjacent = schematic.symbols_around(|sym| symbols.contains(sym.ch), arity, hood);
+
+    (
+        arith::sum("sum around symbols", adjacent.iter().map(|a| a.sum())),
+        arith::sum(
+            "products around symbols",
+            adjacent.iter().map(|a| a.product()),
+        ),
+    )
+}
+
+#[aoc(day = 3, part = 1)]
+pub fn part1(input: &str) -> Wide<u32> {
+    let schematic = parse(input);
+    let parts = schematic.parts(Neighbourhood::default());
+    arith::sum(
+        "sum of part numbers",
+        parts.iter().map(|num| num.value.widen()),
+    )
+}
+
+#[aoc(day = 3, part = 2)]
+pub fn part2(input: &str) -> Wide<u32> {
+    let gears = parse(input).gears();
+    arith::sum("sum of gear ratios", gears.iter().map(Gear::ratio))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{normalise, Solution};
+
+    #[test]
+    fn test_sample_input_part1() {
+        let input = &normalise::fixture(
+            "
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        ",
+        );
+
+        let expected = 4361;
+        let solution = PartOne::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sample_input_part2() {
+        let input = &normalise::fixture(
+            "
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598.. 
+        ",
+        );
+
+        let expected = 467835;
+        let solution = PartTwo::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_around() {
+        let input = &normalise::fixture(
+            "
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        ",
+        );
+
+        let hood = Neighbourhood::default();
+
+        assert_eq!(around(input, "*", Arity::Exactly(2), hood).1, 467835);
+        let (sum, products) = around(input, "#+$", Arity::AtLeast(1), hood);
+        assert_eq!(sum, 1889);
+        assert_eq!(products, 1889);
+
+        let (sum, products) = around(input, "*", Arity::Exactly(1), hood);
+        assert_eq!(sum, 617);
+        assert_eq!(products, 617);
+    }
+}
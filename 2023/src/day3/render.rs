@@ -0,0 +1,275 @@
+use super::schematic::Neighbourhood;
+use std::{fmt::Write, ops::Range};
+
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 18;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Empty,
+    Part,
+    NonPart,
+    Symbol,
+    Gear,
+}
+
+impl Class {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Class::Empty => "90",
+            Class::Part => "32",
+            Class::NonPart => "31",
+            Class::Symbol => "36",
+            Class::Gear => "1;33",
+        }
+    }
+
+    fn css(&self) -> &'static str {
+        match self {
+            Class::Empty => "empty",
+            Class::Part => "part",
+            Class::NonPart => "non-part",
+            Class::Symbol => "symbol",
+            Class::Gear => "gear",
+        }
+    }
+
+    fn colour(&self) -> &'static str {
+        match self {
+            Class::Empty => "#6e7681",
+            Class::Part => "#3fb950",
+            Class::NonPart => "#f85149",
+            Class::Symbol => "#39c5cf",
+            Class::Gear => "#d29922",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    /// Where `ch` starts in its line, in bytes, the same way the schematic
+    /// counts positions.
+    pub pos: usize,
+    pub class: Class,
+}
+
+/// A schematic where every cell has been classified the same way the
+/// solutions see it, so a wrong answer can be checked by eye.
+pub struct Render {
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Render {
+    pub fn new(input: &str) -> Self {
+        let schematic = super::parse(input);
+        let hood = Neighbourhood::default();
+
+        let mut rows = input
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(pos, ch)| Cell {
+                        ch,
+                        pos,
+                        class: Class::Empty,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (row, line) in schematic.lines.iter().enumerate() {
+            for sym in &line.symbols {
+                classify(&mut rows[row], sym.pos..sym.pos + 1, Class::Symbol);
+            }
+
+            for num in &line.numbers {
+                let class = match schematic.symbols_near(row, num, hood).is_empty() {
+                    false => Class::Part,
+                    true => Class::NonPart,
+                };
+                classify(&mut rows[row], num.pos.clone(), class);
+            }
+        }
+
+        for gear in schematic.gears() {
+            classify(&mut rows[gear.row], gear.pos..gear.pos + 1, Class::Gear);
+        }
+
+        Self { rows }
+    }
+
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+
+        for row in &self.rows {
+            for cell in row {
+                write!(out, "\x1b[{}m{}\x1b[0m", cell.class.ansi(), cell.ch).unwrap();
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    pub fn svg(&self) -> String {
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let width = columns * CELL_WIDTH;
+        let height = self.rows.len() * CELL_HEIGHT;
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="14">"#
+        )
+        .unwrap();
+        writeln!(
+            out,
+            r##"<rect width="100%" height="100%" fill="#0d1117"/>"##
+        )
+        .unwrap();
+
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let (px, py) = (x * CELL_WIDTH, y * CELL_HEIGHT);
+
+                if cell.class != Class::Empty {
+                    writeln!(
+                        out,
+                        r#"<rect x="{px}" y="{py}" width="{CELL_WIDTH}" height="{CELL_HEIGHT}" fill="{}" fill-opacity="0.3"/>"#,
+                        cell.class.colour()
+                    )
+                    .unwrap();
+                }
+
+                writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" fill="{}" text-anchor="middle">{}</text>"#,
+                    px + CELL_WIDTH / 2,
+                    py + CELL_HEIGHT - 4,
+                    cell.class.colour(),
+                    escape(cell.ch)
+                )
+                .unwrap();
+            }
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+
+    pub fn html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Day 3 schematic</title>\n<style>\n",
+        );
+
+        out.push_str("body { background: #0d1117; }\n");
+        for class in [
+            Class::Empty,
+            Class::Part,
+            Class::NonPart,
+            Class::Symbol,
+            Class::Gear,
+        ] {
+            writeln!(out, ".{} {{ color: {}; }}", class.css(), class.colour()).unwrap();
+        }
+        out.push_str(".gear { font-weight: bold; }\n");
+        out.push_str("</style>\n</head>\n<body>\n<pre>\n");
+
+        for row in &self.rows {
+            for cell in row {
+                write!(
+                    out,
+                    r#"<span class="{}">{}</span>"#,
+                    cell.class.css(),
+                    escape(cell.ch)
+                )
+                .unwrap();
+            }
+            out.push('\n');
+        }
+
+        out.push_str("</pre>\n</body>\n</html>\n");
+        out
+    }
+}
+
+/// Gives every cell starting in the byte range `bytes` of its line `class`.
+fn classify(row: &mut [Cell], bytes: Range<usize>, class: Class) {
+    let start = row.partition_point(|cell| cell.pos < bytes.start);
+    let end = row.partition_point(|cell| cell.pos < bytes.end);
+    row[start..end]
+        .iter_mut()
+        .for_each(|cell| cell.class = class);
+}
+
+fn escape(ch: char) -> String {
+    match ch {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '"' => "&quot;".to_string(),
+        ch => ch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::normalise;
+
+    #[test]
+    fn test_classify_sample() {
+        let input = &normalise::fixture(
+            "
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        ",
+        );
+
+        let render = Render::new(input);
+        let class_at = |row: usize, col: usize| render.rows[row][col].class;
+
+        assert_eq!(class_at(0, 0), Class::Part);
+        assert_eq!(class_at(0, 5), Class::NonPart);
+        assert_eq!(class_at(1, 3), Class::Gear);
+        assert_eq!(class_at(3, 6), Class::Symbol);
+        assert_eq!(class_at(4, 3), Class::Symbol);
+        assert_eq!(class_at(5, 7), Class::NonPart);
+        assert_eq!(class_at(8, 5), Class::Gear);
+        assert_eq!(class_at(9, 0), Class::Empty);
+    }
+
+    #[test]
+    fn test_classify_multibyte_symbols() {
+        let render = Render::new("é*1.\n..2.");
+        let classes = render.rows[0]
+            .iter()
+            .map(|cell| cell.class)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            classes,
+            [Class::Symbol, Class::Gear, Class::Part, Class::Empty]
+        );
+        assert_eq!(render.rows[1][2].class, Class::Part);
+    }
+
+    #[test]
+    fn test_escape_markup() {
+        let render = Render::new("1&.");
+        let svg = render.svg();
+        let html = render.html();
+
+        assert!(svg.contains(">&amp;</text>"));
+        assert!(html.contains(r#"<span class="symbol">&amp;</span>"#));
+    }
+}
@@ -0,0 +1,584 @@
+use crate::arith::{self, Wide, Widen};
+use itertools::Itertools;
+use std::{convert::Infallible, iter::once, ops::Range, str::FromStr};
+
+/// The whole schematic, so adjacency can reach past the lines directly
+/// above and below.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Schematic {
+    pub lines: Vec<SchematicLine>,
+}
+
+impl Schematic {
+    /// The lines within `hood` of `row`, paired with their row index.
+    fn rows_near(
+        &self,
+        row: usize,
+        hood: Neighbourhood,
+    ) -> impl Iterator<Item = (usize, &SchematicLine)> {
+        let first = row.saturating_sub(hood.radius);
+        let last = row
+            .saturating_add(hood.radius)
+            .min(self.lines.len().saturating_sub(1));
+
+        self.lines
+            .get(first..=last)
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .map(move |(idx, line)| (first + idx, line))
+    }
+
+    /// The numbers within `hood` of a symbol on `row`.
+    pub fn numbers_near(&self, row: usize, sym: &Symbol, hood: Neighbourhood) -> Vec<&Number> {
+        let cols = sym.pos.saturating_sub(hood.radius)..sym.pos.saturating_add(hood.radius + 1);
+
+        self.rows_near(row, hood)
+            .flat_map(|(num_row, line)| {
+                line.numbers_within(cols.clone())
+                    .iter()
+                    .filter(move |num| hood.reaches((row, sym.pos), (num_row, &num.pos)))
+            })
+            .collect()
+    }
+
+    /// The symbols within `hood` of a number on `row`.
+    pub fn symbols_near(&self, row: usize, num: &Number, hood: Neighbourhood) -> Vec<&Symbol> {
+        let cols =
+            num.pos.start.saturating_sub(hood.radius)..num.pos.end.saturating_add(hood.radius);
+
+        self.rows_near(row, hood)
+            .flat_map(|(sym_row, line)| {
+                line.symbols_within(cols.clone())
+                    .iter()
+                    .filter(move |sym| hood.reaches((sym_row, sym.pos), (row, &num.pos)))
+            })
+            .collect()
+    }
+
+    /// Every number with at least one symbol within `hood`.
+    pub fn parts(&self, hood: Neighbourhood) -> Vec<&Number> {
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| line.numbers.iter().map(move |num| (row, num)))
+            .filter(|(row, num)| !self.symbols_near(*row, num, hood).is_empty())
+            .map(|(_, num)| num)
+            .collect()
+    }
+
+    /// Every symbol picked out by `matches` whose count of numbers within
+    /// `hood` fits `arity`, along with those numbers.
+    pub fn symbols_around(
+        &self,
+        matches: impl Fn(&Symbol) -> bool,
+        arity: Arity,
+        hood: Neighbourhood,
+    ) -> Vec<Adjacent<'_>> {
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| line.symbols.iter().map(move |sym| (row, sym)))
+            .filter(|(_, sym)| matches(sym))
+            .map(|(row, sym)| Adjacent {
+                row,
+                symbol: sym,
+                numbers: self.numbers_near(row, sym, hood),
+            })
+            .filter(|adjacent| arity.allows(adjacent.numbers.len()))
+            .collect()
+    }
+
+    pub fn gears(&self) -> Vec<Gear> {
+        self.symbols_around(
+            |sym| sym.ch == '*',
+            Arity::Exactly(2),
+            Neighbourhood::default(),
+        )
+        .into_iter()
+        .map(|adjacent| Gear {
+            row: adjacent.row,
+            pos: adjacent.symbol.pos,
+            parts: (adjacent.numbers[0].value, adjacent.numbers[1].value),
+        })
+        .collect()
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s
+            .lines()
+            .map(|line| line.parse::<SchematicLine>().unwrap())
+            .collect();
+
+        Ok(Schematic { lines })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SchematicLine {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    /// What's at each byte of the line, so the numbers and symbols in a
+    /// range of columns are read straight off rather than searched for.
+    columns: Vec<Column>,
+}
+
+/// One column of a [`SchematicLine`], pointing into its numbers or symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Empty,
+    Number(u32),
+    Symbol(u32),
+}
+
+impl SchematicLine {
+    /// The numbers with a digit in `cols`. Numbers are parsed left to right
+    /// and never overlap, so they're the run from the first number in
+    /// `cols` to the last, and this only looks at the columns asked about.
+    pub fn numbers_within(&self, cols: Range<usize>) -> &[Number] {
+        let mut ids = self.columns_within(cols).filter_map(|column| match column {
+            Column::Number(id) => Some(id as usize),
+            _ => None,
+        });
+        match (ids.next(), ids.next_back()) {
+            (Some(first), last) => &self.numbers[first..=last.unwrap_or(first)],
+            (None, _) => &[],
+        }
+    }
+
+    /// The symbols in `cols`, found the same way as
+    /// [`SchematicLine::numbers_within`].
+    pub fn symbols_within(&self, cols: Range<usize>) -> &[Symbol] {
+        let mut ids = self.columns_within(cols).filter_map(|column| match column {
+            Column::Symbol(id) => Some(id as usize),
+            _ => None,
+        });
+        match (ids.next(), ids.next_back()) {
+            (Some(first), last) => &self.symbols[first..=last.unwrap_or(first)],
+            (None, _) => &[],
+        }
+    }
+
+    fn columns_within(&self, cols: Range<usize>) -> impl DoubleEndedIterator<Item = Column> + '_ {
+        let end = cols.end.min(self.columns.len());
+        self.columns
+            .get(cols.start.min(end)..end)
+            .unwrap_or_default()
+            .iter()
+            .copied()
+    }
+}
+
+impl FromStr for SchematicLine {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.char_indices().peekable();
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut columns = Vec::new();
+
+        loop {
+            match chars.next() {
+                Some((idx, ch @ '0'..='9')) => {
+                    let rest = chars
+                        .peeking_take_while(|(_, char)| char.is_ascii_digit())
+                        .map(|(_, char)| char);
+                    let value = once(ch).chain(rest).collect::<String>();
+
+                    let num = Number {
+                        value: value.parse().unwrap(),
+                        pos: idx..idx + value.len(),
+                    };
+
+                    columns.resize(idx, Column::Empty);
+                    columns.resize(num.pos.end, Column::Number(numbers.len() as u32));
+                    numbers.push(num);
+                }
+                Some((_, ch)) if ch == '.' || ch.is_whitespace() => continue,
+                Some((idx, ch)) => {
+                    let sym = Symbol { ch, pos: idx };
+
+                    columns.resize(idx, Column::Empty);
+                    columns.push(Column::Symbol(symbols.len() as u32));
+                    symbols.push(sym);
+                }
+                None => break,
+            };
+        }
+
+        columns.resize(s.len(), Column::Empty);
+
+        Ok(SchematicLine {
+            numbers,
+            symbols,
+            columns,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub pos: Range<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub ch: char,
+    pub pos: usize,
+}
+
+/// How distance between two cells is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Diagonal steps count as one, so the cells within 1 form a 3x3 square.
+    Chebyshev,
+    /// Only straight steps, so the cells within 1 form a plus.
+    Manhattan,
+}
+
+impl FromStr for Metric {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "chebyshev" => Ok(Metric::Chebyshev),
+            "manhattan" => Ok(Metric::Manhattan),
+            _ => Err("expected `chebyshev` or `manhattan`"),
+        }
+    }
+}
+
+/// Which cells count as touching. The puzzle's rule is the default, every
+/// cell within one step including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbourhood {
+    pub radius: usize,
+    pub metric: Metric,
+}
+
+impl Neighbourhood {
+    /// Whether the cell at `(row, col)` is within range of any digit of a
+    /// number spanning `cols` on `num_row`.
+    pub fn reaches(
+        &self,
+        (row, col): (usize, usize),
+        (num_row, cols): (usize, &Range<usize>),
+    ) -> bool {
+        let dy = row.abs_diff(num_row);
+        let dx = if col < cols.start {
+            cols.start - col
+        } else {
+            col.saturating_sub(cols.end.saturating_sub(1))
+        };
+
+        let distance = match self.metric {
+            Metric::Chebyshev => dx.max(dy),
+            Metric::Manhattan => dx + dy,
+        };
+
+        distance <= self.radius
+    }
+}
+
+impl Default for Neighbourhood {
+    fn default() -> Self {
+        Self {
+            radius: 1,
+            metric: Metric::Chebyshev,
+        }
+    }
+}
+
+/// How many numbers a symbol has to touch to count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn allows(&self, count: usize) -> bool {
+        match *self {
+            Arity::Exactly(n) => count == n,
+            Arity::AtLeast(n) => count >= n,
+        }
+    }
+}
+
+/// `2` for exactly two, `2+` for at least two.
+impl FromStr for Arity {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (count, at_least) = match s.strip_suffix('+') {
+            Some(count) => (count, true),
+            None => (s, false),
+        };
+        let count = count
+            .parse()
+            .map_err(|_| "expected a count like `2` or `2+`")?;
+
+        Ok(match at_least {
+            true => Arity::AtLeast(count),
+            false => Arity::Exactly(count),
+        })
+    }
+}
+
+/// A symbol and the numbers touching it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Adjacent<'a> {
+    pub row: usize,
+    pub symbol: &'a Symbol,
+    pub numbers: Vec<&'a Number>,
+}
+
+impl Adjacent<'_> {
+    pub fn product(&self) -> Wide<u32> {
+        let values = self.numbers.iter().map(|num| num.value.widen());
+        arith::product("product of adjacent numbers", values)
+    }
+
+    pub fn sum(&self) -> Wide<u32> {
+        let values = self.numbers.iter().map(|num| num.value.widen());
+        arith::sum("sum of adjacent numbers", values)
+    }
+}
+
+pub struct Gear {
+    pub row: usize,
+    pub pos: usize,
+    parts: (u32, u32),
+}
+
+impl Gear {
+    pub fn ratio(&self) -> Wide<u32> {
+        arith::mul("gear ratio", self.parts.0.widen(), self.parts.1.widen())
+    }
+}
+
+/// A `width` x `height` schematic of digits, dots and symbols from a
+/// fixed seed, so runs are repeatable without pulling in `rand`. Used to
+/// check and time lookups on grids far bigger than the real input.
+pub fn generate(width: usize, height: usize) -> String {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 100
+    };
+
+    let mut out = String::with_capacity((width + 1) * height);
+    for _ in 0..height {
+        let mut digits = 0;
+        for _ in 0..width {
+            // Numbers are capped at three digits like the real input.
+            let ch = match next() {
+                _ if digits == 3 => '.',
+                0..=59 => '.',
+                60..=91 => char::from(b'0' + (next() % 10) as u8),
+                92..=95 => '*',
+                _ => '#',
+            };
+            digits = if ch.is_ascii_digit() { digits + 1 } else { 0 };
+            out.push(ch);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::normalise;
+
+    const INPUT: &str = "
+        467..114..
+        ...*......
+        ..35..633.
+        ......#...
+        617*......
+        .....+.58.
+        ..592.....
+        ......755.
+        ...$.*....
+        .664.598..
+    ";
+
+    fn schematic() -> Schematic {
+        normalise::fixture(INPUT).parse().unwrap()
+    }
+
+    #[test]
+    fn test_symbols_around() {
+        let schematic = schematic();
+        let query = |matches: fn(&Symbol) -> bool, arity| {
+            schematic
+                .symbols_around(matches, arity, Neighbourhood::default())
+                .iter()
+                .map(|adjacent| format!("{}{}", adjacent.symbol.ch, adjacent.sum()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(query(|sym| sym.ch == '*', Arity::Exactly(1)), vec!["*617"]);
+        assert_eq!(query(|_| true, Arity::AtLeast(2)), vec!["*502", "*1353"]);
+        assert_eq!(
+            query(|sym| sym.ch != '*', Arity::AtLeast(1)),
+            vec!["#633", "+592", "$664"]
+        );
+        assert_eq!(
+            query(|sym| sym.ch == '*', Arity::AtLeast(3)),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_symbols_near() {
+        let schematic = schematic();
+        let touching = |row: usize, value: u32, hood| {
+            let num = schematic.lines[row]
+                .numbers
+                .iter()
+                .find(|num| num.value == value)
+                .unwrap();
+            schematic
+                .symbols_near(row, num, hood)
+                .iter()
+                .map(|sym| sym.ch)
+                .collect::<Vec<_>>()
+        };
+        let hood = Neighbourhood::default();
+
+        assert_eq!(touching(2, 35, hood), vec!['*']);
+        assert_eq!(touching(2, 633, hood), vec!['#']);
+        assert_eq!(touching(0, 114, hood), Vec::<char>::new());
+
+        // Two steps reach from row 4 to row 8, missing the `#` three rows up.
+        let hood = Neighbourhood {
+            radius: 2,
+            metric: Metric::Chebyshev,
+        };
+        assert_eq!(touching(6, 592, hood), vec!['*', '+', '$', '*']);
+
+        let hood = Neighbourhood {
+            radius: 2,
+            metric: Metric::Manhattan,
+        };
+        assert_eq!(touching(6, 592, hood), vec!['*', '+', '$']);
+    }
+
+    #[test]
+    fn test_neighbourhood_metrics() {
+        let chebyshev = Neighbourhood::default();
+        let manhattan = Neighbourhood {
+            radius: 1,
+            metric: Metric::Manhattan,
+        };
+
+        // A number on row 5 spanning columns 3, 4 and 5.
+        let num = (5, &(3..6));
+
+        for (cell, diagonal) in [
+            ((4, 2), true),
+            ((6, 6), true),
+            ((4, 4), false),
+            ((5, 6), false),
+        ] {
+            assert!(chebyshev.reaches(cell, num));
+            assert_eq!(manhattan.reaches(cell, num), !diagonal);
+        }
+        assert!(!chebyshev.reaches((3, 4), num));
+        assert!(!chebyshev.reaches((5, 7), num));
+        assert!(!manhattan.reaches((5, 1), num));
+
+        let wide = Neighbourhood {
+            radius: 2,
+            metric: Metric::Manhattan,
+        };
+        assert!(wide.reaches((4, 2), num));
+        assert!(wide.reaches((3, 4), num));
+        assert!(!wide.reaches((3, 2), num));
+    }
+
+    #[test]
+    fn test_within_columns() {
+        let line = "467..114.*.$".parse::<SchematicLine>().unwrap();
+        let values = |cols| {
+            line.numbers_within(cols)
+                .iter()
+                .map(|num| num.value)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(values(0..12), vec![467, 114]);
+        assert_eq!(values(2..6), vec![467, 114]);
+        assert_eq!(values(3..5), vec![]);
+        assert_eq!(values(7..7), vec![]);
+        assert_eq!(values(8..usize::MAX), vec![]);
+
+        let chars = |cols| {
+            line.symbols_within(cols)
+                .iter()
+                .map(|sym| sym.ch)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(chars(0..10), vec!['*']);
+        assert_eq!(chars(9..12), vec!['*', '$']);
+        assert_eq!(chars(10..11), Vec::<char>::new());
+    }
+
+    /// Numbers near each symbol found by checking every number on the rows
+    /// in range.
+    fn scan_numbers_near<'a>(
+        schematic: &'a Schematic,
+        row: usize,
+        sym: &Symbol,
+        hood: Neighbourhood,
+    ) -> Vec<&'a Number> {
+        schematic
+            .rows_near(row, hood)
+            .flat_map(|(num_row, line)| {
+                line.numbers
+                    .iter()
+                    .filter(move |num| hood.reaches((row, sym.pos), (num_row, &num.pos)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lookups_agree_with_scanning() {
+        let schematic = generate(300, 300).parse::<Schematic>().unwrap();
+
+        for hood in [
+            Neighbourhood::default(),
+            Neighbourhood {
+                radius: 2,
+                metric: Metric::Manhattan,
+            },
+        ] {
+            for (row, line) in schematic.lines.iter().enumerate() {
+                for sym in &line.symbols {
+                    assert_eq!(
+                        schematic.numbers_near(row, sym, hood),
+                        scan_numbers_near(&schematic, row, sym, hood)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_arity() {
+        assert_eq!("2".parse(), Ok(Arity::Exactly(2)));
+        assert_eq!("1+".parse(), Ok(Arity::AtLeast(1)));
+        assert!("+".parse::<Arity>().is_err());
+    }
+}
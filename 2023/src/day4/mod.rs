@@ -0,0 +1,402 @@
+use crate::{
+    aoc,
+    arith::{self, Int, Wide},
+    parsing,
+};
+use std::{cell::Cell, collections::btree_map::BTreeMap, env, fmt, str::FromStr};
+
+/// How many winning numbers each card has, keyed by card number.
+///
+/// Card numbers needn't be contiguous, but each may only appear once. When
+/// [`past_end`] is [`PastEnd::Error`], a card that wins copies of more cards
+/// than follow it fails the parse when parsing strictly, and is dropped with
+/// a warning otherwise.
+pub fn parse(input: &str) -> Result<BTreeMap<u32, u32>, parsing::BadLine> {
+    let mut cards = BTreeMap::new();
+    let mut lines = BTreeMap::new();
+
+    for (line, (card, matches)) in parsing::numbered(input, parse_card)? {
+        let unique = match lines.get(&card) {
+            Some(first) => Err(format!("card {card} is already on line {first}")),
+            None => Ok(()),
+        };
+        if let Some(unique) = parsing::check(input, line, unique) {
+            unique?;
+            cards.insert(card, matches);
+            lines.insert(card, line);
+        }
+    }
+
+    // Checked from the back, so a dropped card isn't counted as following
+    // the ones before it.
+    let past_end = past_end();
+    let mut following = 0;
+    for (card, matches) in cards.clone().into_iter().rev() {
+        let reach = reach(card, matches, following, past_end);
+        match parsing::check(input, lines[&card], reach) {
+            Some(reach) => {
+                reach?;
+                following += 1;
+            }
+            None => {
+                cards.remove(&card);
+            }
+        }
+    }
+
+    Ok(cards)
+}
+
+fn parse_card(line: &str) -> Result<(u32, u32), &'static str> {
+    let (card, numbers) = line.split_once(':').ok_or("missing ':'")?;
+    let card_num = card
+        .strip_prefix("Card")
+        .ok_or("expected `Card <number>`")?
+        .trim()
+        .parse()
+        .map_err(|_| "invalid card number")?;
+    let (winners, actual) = numbers.split_once('|').ok_or("missing '|'")?;
+    let winning_nums = winners.split_whitespace().collect::<Vec<_>>();
+
+    let matches = actual
+        .split_whitespace()
+        .filter(|str| winning_nums.contains(str))
+        .count() as u32;
+
+    Ok((card_num, matches))
+}
+
+/// What to do about a card that wins copies of more cards than follow it.
+///
+/// Kept per thread like [`parsing::Mode`], read from `AOC_PAST_END` the
+/// first time it's needed unless [`set_past_end`] got there first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PastEnd {
+    /// Win copies of every card that does follow it.
+    #[default]
+    Clamp,
+    /// Treat the card as a bad line of input.
+    Error,
+}
+
+impl FromStr for PastEnd {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clamp" => Ok(PastEnd::Clamp),
+            "error" => Ok(PastEnd::Error),
+            _ => Err(format!(
+                "unknown past end handling `{s}`, expected clamp or error"
+            )),
+        }
+    }
+}
+
+thread_local! {
+    static PAST_END: Cell<Option<PastEnd>> = const { Cell::new(None) };
+}
+
+pub fn past_end() -> PastEnd {
+    PAST_END.with(|past_end| match past_end.get() {
+        Some(current) => current,
+        None => {
+            let current = match env::var("AOC_PAST_END") {
+                Ok(value) => value.parse().unwrap_or_else(|err| {
+                    parsing::note(format!("{err}, clamping"));
+                    PastEnd::default()
+                }),
+                Err(_) => PastEnd::default(),
+            };
+            past_end.set(Some(current));
+            current
+        }
+    })
+}
+
+pub fn set_past_end(past_end: PastEnd) {
+    PAST_END.with(|current| current.set(Some(past_end)));
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PastEndError {
+    pub card: u32,
+    pub matches: u32,
+    pub following: usize,
+}
+
+impl fmt::Display for PastEndError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "card {} wins copies of the next {} cards but only {} follow it",
+            self.card, self.matches, self.following
+        )
+    }
+}
+
+impl std::error::Error for PastEndError {}
+
+/// How many of the `following` cards a card with `matches` wins copies of.
+/// Those are the next cards in the table, whatever their numbers.
+fn reach(
+    card: u32,
+    matches: u32,
+    following: usize,
+    past_end: PastEnd,
+) -> Result<usize, PastEndError> {
+    match (matches as usize <= following, past_end) {
+        (true, _) => Ok(matches as usize),
+        (false, PastEnd::Clamp) => Ok(following),
+        (false, PastEnd::Error) => Err(PastEndError {
+            card,
+            matches,
+            following,
+        }),
+    }
+}
+
+#[aoc(day = 4, part = 1)]
+pub fn part1(cards: &BTreeMap<u32, u32>) -> Wide<u32> {
+    let scores = cards.values().map(|matches| match matches {
+        0 => Int::zero(),
+        n => arith::pow2("card score", n - 1),
+    });
+    arith::sum("sum of card scores", scores)
+}
+
+/// Every original card and every copy won, following the cascade.
+pub fn count_scratchcards(
+    cards: &BTreeMap<u32, u32>,
+    past_end: PastEnd,
+) -> Result<Wide<u32>, PastEndError> {
+    let cards = cards
+        .iter()
+        .map(|(&card, &n)| (card, n))
+        .collect::<Vec<_>>();
+    count_scratchcards_dynamic(&cards, past_end)
+}
+
+/// Parses the cards and counts them straight away, so a card that wins
+/// past the end with nothing to drop it fails here rather than in
+/// [`part2`].
+pub fn parse_scratchcard_count(input: &str) -> Result<Wide<u32>, CountError> {
+    let cards = parse(input)?;
+    Ok(count_scratchcards(&cards, past_end())?)
+}
+
+#[aoc(day = 4, part = 2, parse = parse_scratchcard_count)]
+pub fn part2(count: &Wide<u32>) -> Wide<u32> {
+    count.to_owned()
+}
+
+/// Why the scratchcards couldn't be counted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CountError {
+    BadLine(parsing::BadLine),
+    PastEnd(PastEndError),
+}
+
+impl From<parsing::BadLine> for CountError {
+    fn from(err: parsing::BadLine) -> Self {
+        CountError::BadLine(err)
+    }
+}
+
+impl From<PastEndError> for CountError {
+    fn from(err: PastEndError) -> Self {
+        CountError::PastEnd(err)
+    }
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountError::BadLine(err) => err.fmt(f),
+            CountError::PastEnd(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CountError {}
+
+fn count_scratchcards_dynamic(
+    cards: &[(u32, u32)],
+    past_end: PastEnd,
+) -> Result<Wide<u32>, PastEndError> {
+    // Filled from the back, so the cards a card wins are the last ones in.
+    let mut yields = Vec::<Wide<u32>>::with_capacity(cards.len());
+
+    for (following, &(card, matches)) in cards.iter().rev().enumerate() {
+        let won = reach(card, matches, following, past_end)?;
+        let bonus_cards = arith::sum(
+            "scratchcards won",
+            yields[following - won..].iter().cloned(),
+        );
+        yields.push(arith::add("scratchcard count", bonus_cards, Int::one()));
+    }
+
+    Ok(arith::sum("total scratchcards", yields))
+}
+
+/// Counts the cards at `positions` and everything they win by following
+/// each copy down the cascade.
+///
+/// Exponentially slower than [`count_scratchcards_dynamic`], but simple
+/// enough to be obviously right, so it's kept as a check on it.
+#[cfg(test)]
+fn count_scratchcards_recursive(
+    cards: &[(u32, u32)],
+    positions: std::ops::Range<usize>,
+    past_end: PastEnd,
+) -> Result<Wide<u32>, PastEndError> {
+    let counts = positions
+        .map(|pos| {
+            let (card, matches) = cards[pos];
+            let won = reach(card, matches, cards.len() - pos - 1, past_end)?;
+            let yields = count_scratchcards_recursive(cards, pos + 1..pos + 1 + won, past_end)?;
+            Ok(arith::add("scratchcard count", yields, Int::one()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(arith::sum("total scratchcards", counts))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Solution;
+
+    #[test]
+    fn test_sample_input_part1() {
+        let input = "
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "
+        .trim();
+
+        let expected = 13;
+
+        let solution = PartOne::new(input).solve();
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sample_input_part2() {
+        let input = "
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+        "
+        .trim();
+
+        let expected = 30;
+
+        let solution = PartTwo::new(input).solve();
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sparse_card_numbers() {
+        let input = "
+            Card 1: 1 2 | 1
+            Card 5: 3 | 3
+            Card 10: 4 | 5
+        ";
+
+        let cards = parse(input).unwrap();
+        assert_eq!(cards.keys().collect::<Vec<_>>(), [&1, &5, &10]);
+        assert_eq!(part1(&cards), 2);
+        // Card 1 wins a copy of card 5 and each card 5 wins a copy of card 10.
+        assert_eq!(parse_scratchcard_count(input).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_wins_past_the_end() {
+        let input = "Card 1: 1 | 1\nCard 2: 3 | 4\nCard 3: 5 | 5";
+
+        set_past_end(PastEnd::Clamp);
+        parsing::set_mode(parsing::Mode::Strict);
+        let cards = parse(input).unwrap();
+        assert_eq!(cards.len(), 3);
+        assert_eq!(parse_scratchcard_count(input).unwrap(), 4);
+
+        set_past_end(PastEnd::Error);
+        assert_eq!(
+            parse(input).unwrap_err().to_string(),
+            "line 3: card 3 wins copies of the next 1 cards but only 0 follow it: `Card 3: 5 | 5`"
+        );
+        assert_eq!(
+            count_scratchcards(&cards, PastEnd::Error).unwrap_err(),
+            PastEndError {
+                card: 3,
+                matches: 1,
+                following: 0
+            }
+        );
+        assert!(matches!(
+            parse_scratchcard_count(input),
+            Err(CountError::BadLine(bad)) if bad.line == 3
+        ));
+
+        // Card 3 is dropped, which leaves card 1 winning card 2 as before.
+        parsing::set_mode(parsing::Mode::Lenient);
+        parsing::take_warnings();
+        let cards = parse(input).unwrap();
+        assert_eq!(cards.keys().collect::<Vec<_>>(), [&1, &2]);
+        assert_eq!(parsing::take_warnings()[0].line, 3);
+        assert_eq!(parse_scratchcard_count(input).unwrap(), 3);
+        set_past_end(PastEnd::Clamp);
+    }
+
+    #[test]
+    fn test_past_end_from_str() {
+        assert_eq!("clamp".parse(), Ok(PastEnd::Clamp));
+        assert_eq!("error".parse(), Ok(PastEnd::Error));
+        assert!("wrap".parse::<PastEnd>().is_err());
+    }
+
+    #[test]
+    fn test_recursive_count_agrees_with_dynamic_count() {
+        let cases: [&[(u32, u32)]; 4] = [
+            &[(1, 4), (2, 2), (3, 2), (4, 1), (5, 0), (6, 0)],
+            &[(1, 0), (2, 0)],
+            &[(1, 2), (2, 1), (3, 1)],
+            &[(1, 1), (3, 3), (7, 0)],
+        ];
+
+        for cards in cases {
+            for past_end in [PastEnd::Clamp, PastEnd::Error] {
+                assert_eq!(
+                    count_scratchcards_recursive(cards, 0..cards.len(), past_end),
+                    count_scratchcards_dynamic(cards, past_end),
+                    "{cards:?} {past_end:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_numbers() {
+        parsing::set_mode(parsing::Mode::Strict);
+        assert_eq!(
+            parse("Card 1 2: 1 | 1").unwrap_err().reason,
+            "invalid card number"
+        );
+        assert_eq!(
+            parse("Game 1: 1 | 1").unwrap_err().reason,
+            "expected `Card <number>`"
+        );
+        assert_eq!(
+            parse("Card 1: 1 | 1\nCard 1: 2 | 2").unwrap_err().reason,
+            "card 1 is already on line 1"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use super::map::Map;
+use std::{cmp::Ordering, fmt::Write, ops::Range};
+
+/// How a set of seed ranges splits as it passes through a chain of maps.
+/// `levels[0]` holds the seed ranges and `levels[i + 1]` holds the ranges
+/// produced by the `i`th map, and `categories[i]` names `levels[i]`.
+#[derive(Debug)]
+pub struct Flow {
+    pub categories: Vec<String>,
+    pub levels: Vec<Vec<Range<u64>>>,
+    pub edges: Vec<Vec<Edge>>,
+}
+
+/// A piece of a range at one level, and the range it becomes at the next.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub parent: usize,
+    pub src: Range<u64>,
+    pub dest: Range<u64>,
+}
+
+impl Edge {
+    fn offset(&self) -> String {
+        match self.dest.start.cmp(&self.src.start) {
+            Ordering::Equal => "=".to_string(),
+            Ordering::Greater => format!("+{}", self.dest.start - self.src.start),
+            Ordering::Less => format!("-{}", self.src.start - self.dest.start),
+        }
+    }
+}
+
+impl Flow {
+    pub fn trace(maps: &[&Map], seed_ranges: &[Range<u64>]) -> Self {
+        let mut categories = maps
+            .first()
+            .map(|m| vec![m.from.clone()])
+            .unwrap_or_default();
+        let mut levels = vec![seed_ranges.to_vec()];
+        let mut edges = Vec::new();
+
+        for map in maps {
+            categories.push(map.to.clone());
+
+            let stage = levels
+                .last()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .flat_map(|(parent, range)| {
+                    map.split_range(range)
+                        .into_iter()
+                        .map(move |(src, dest)| Edge { parent, src, dest })
+                })
+                .collect::<Vec<_>>();
+
+            levels.push(stage.iter().map(|edge| edge.dest.clone()).collect());
+            edges.push(stage);
+        }
+
+        Self {
+            categories,
+            levels,
+            edges,
+        }
+    }
+
+    pub fn locations(&self) -> &[Range<u64>] {
+        self.levels.last().unwrap()
+    }
+
+    pub fn table(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{:<28}{:<28}{:<28}offset", "map", "from", "to").unwrap();
+
+        for (idx, stage) in self.edges.iter().enumerate() {
+            let map = format!("{}-to-{}", self.categories[idx], self.categories[idx + 1]);
+            for edge in stage {
+                writeln!(
+                    out,
+                    "{:<28}{:<28}{:<28}{}",
+                    map,
+                    format!("{:?}", edge.src),
+                    format!("{:?}", edge.dest),
+                    edge.offset()
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(out).unwrap();
+        writeln!(out, "locations:").unwrap();
+        for range in self.locations() {
+            writeln!(out, "  {:?}", range).unwrap();
+        }
+
+        out
+    }
+
+    pub fn dot(&self) -> String {
+        let mut out = String::from(
+            "digraph almanac {\n    rankdir=LR;\n    node [shape=box, fontname=monospace];\n",
+        );
+
+        for (level, ranges) in self.levels.iter().enumerate() {
+            let label = match self.categories.get(level).map(String::as_str) {
+                None | Some("") => format!("level {level}"),
+                Some(category) => category.to_string(),
+            };
+
+            writeln!(out, "    subgraph cluster_{level} {{").unwrap();
+            writeln!(out, "        label=\"{label}\";").unwrap();
+            for (idx, range) in ranges.iter().enumerate() {
+                writeln!(out, "        n{level}_{idx} [label=\"{:?}\"];", range).unwrap();
+            }
+            writeln!(out, "    }}").unwrap();
+        }
+
+        for (level, stage) in self.edges.iter().enumerate() {
+            for (idx, edge) in stage.iter().enumerate() {
+                writeln!(
+                    out,
+                    "    n{level}_{} -> n{}_{idx} [label=\"{:?} {}\"];",
+                    edge.parent,
+                    level + 1,
+                    edge.src,
+                    edge.offset()
+                )
+                .unwrap();
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        let maps = [
+            "seed-to-soil map:\n50 98 2\n52 50 48",
+            "soil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15",
+        ]
+        .iter()
+        .map(|str| str.parse().unwrap())
+        .collect::<Vec<Map>>();
+
+        let flow = Flow::trace(&maps.iter().collect::<Vec<_>>(), &[79..93, 45..55]);
+
+        assert_eq!(flow.categories, vec!["seed", "soil", "fertilizer"]);
+        assert_eq!(flow.levels[1], vec![81..95, 45..50, 52..57]);
+        assert_eq!(flow.locations(), &[81..95, 30..35, 37..39, 54..57]);
+        assert_eq!(
+            flow.edges[1][2],
+            Edge {
+                parent: 2,
+                src: 52..54,
+                dest: 37..39,
+            }
+        );
+    }
+
+    #[test]
+    fn test_dot_links_levels() {
+        let map = "seed-to-soil map:\n50 98 2\n52 50 48".parse().unwrap();
+
+        let dot = Flow::trace(&[&map], &[45..55, 98..100]).dot();
+
+        assert!(dot.contains("label=\"soil\";"));
+        assert!(dot.contains("n0_0 [label=\"45..55\"];"));
+        assert!(dot.contains("n0_0 -> n1_1 [label=\"50..55 +2\"];"));
+        assert!(dot.contains("n0_1 -> n1_2 [label=\"98..100 -48\"];"));
+    }
+}
@@ -0,0 +1,975 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    ops::Range,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+/// An integer the values in a [`Map`] can be, signed or not.
+///
+/// Offsets between ranges use wrapping arithmetic. A translated value
+/// always lands inside a range of the map, so it is representable even when
+/// the offset that got it there isn't, e.g. from near `i64::MIN` to near
+/// `i64::MAX`.
+pub trait Value: Copy + Ord + fmt::Debug + fmt::Display + FromStr {
+    const ZERO: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_value {
+    ($($ty:ty),*) => {$(
+        impl Value for $ty {
+            const ZERO: Self = 0;
+            const MIN: Self = <$ty>::MIN;
+            const MAX: Self = <$ty>::MAX;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$ty>::checked_add(self, rhs)
+            }
+
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$ty>::wrapping_add(self, rhs)
+            }
+
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$ty>::wrapping_sub(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_value!(u32, u64, i64, u128);
+
+#[derive(Debug)]
+pub struct Almanac<T = u64> {
+    pub maps: Vec<Map<T>>,
+}
+
+impl<T: Value> Almanac<T> {
+    /// The maps that take a `from` value to a `to` value, in the order they
+    /// apply. The maps can be listed in any order.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&Map<T>>, AlmanacError<T>> {
+        let route = self.route(from, to)?;
+        Ok(route.into_iter().map(|idx| &self.maps[idx]).collect())
+    }
+
+    /// Like [`Almanac::path`], but keeps only the maps on the path.
+    pub fn chain(self, from: &str, to: &str) -> Result<Almanac<T>, AlmanacError<T>> {
+        let route = self.route(from, to)?;
+        let mut maps = self.maps.into_iter().map(Some).collect::<Vec<_>>();
+
+        Ok(route
+            .into_iter()
+            .filter_map(|idx| maps[idx].take())
+            .collect())
+    }
+
+    pub fn convert(&self, from: &str, to: &str, value: T) -> Result<T, AlmanacError<T>> {
+        let path = self.path(from, to)?;
+        Ok(path.iter().fold(value, |value, map| map.map_to_next(value)))
+    }
+
+    /// Every map turned around, so paths run from `to` back to `from`.
+    pub fn invert(&self) -> Result<Almanac<T>, InvertError<T>> {
+        self.maps.iter().map(Map::invert).collect()
+    }
+
+    /// The `from` values that end up somewhere in `values` once mapped to
+    /// `to`, e.g. the seeds that land in a range of locations.
+    pub fn preimage(
+        &self,
+        from: &str,
+        to: &str,
+        values: &Range<T>,
+    ) -> Result<Vec<Range<T>>, AlmanacError<T>> {
+        let inverse = self.invert()?;
+
+        let ranges =
+            inverse
+                .path(to, from)?
+                .into_iter()
+                .fold(vec![values.clone()], |ranges, map| {
+                    ranges
+                        .iter()
+                        .flat_map(|range| map.split_range(range))
+                        .map(|(_, dest)| dest)
+                        .collect()
+                });
+
+        Ok(union(ranges))
+    }
+
+    fn route<'a>(&'a self, from: &'a str, to: &'a str) -> Result<Vec<usize>, AlmanacError<T>> {
+        for category in [from, to] {
+            if !self
+                .maps
+                .iter()
+                .any(|m| m.from == category || m.to == category)
+            {
+                return Err(AlmanacError::UnknownCategory(category.to_string()));
+            }
+        }
+
+        let mut reached_by = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                break;
+            }
+
+            for (idx, map) in self.maps.iter().enumerate() {
+                if map.from == category && !reached_by.contains_key(map.to.as_str()) {
+                    reached_by.insert(map.to.as_str(), Some(idx));
+                    queue.push_back(&map.to);
+                }
+            }
+        }
+
+        if !reached_by.contains_key(to) {
+            let dead_ends = reached_by
+                .keys()
+                .filter(|category| !self.maps.iter().any(|m| m.from == **category))
+                .map(|category| category.to_string())
+                .sorted()
+                .collect();
+
+            return Err(AlmanacError::BrokenChain {
+                from: from.to_string(),
+                to: to.to_string(),
+                dead_ends,
+            });
+        }
+
+        let mut route = Vec::new();
+        let mut category = to;
+        while let Some(Some(idx)) = reached_by.get(category) {
+            route.push(*idx);
+            category = &self.maps[*idx].from;
+        }
+        route.reverse();
+
+        Ok(route)
+    }
+}
+
+impl<T> FromIterator<Map<T>> for Almanac<T> {
+    fn from_iter<I: IntoIterator<Item = Map<T>>>(iter: I) -> Self {
+        let maps = iter.into_iter().collect();
+        Self { maps }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AlmanacError<T = u64> {
+    NotInvertible(InvertError<T>),
+    UnknownCategory(String),
+    BrokenChain {
+        from: String,
+        to: String,
+        dead_ends: Vec<String>,
+    },
+}
+
+impl<T: fmt::Debug> fmt::Display for AlmanacError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacError::NotInvertible(err) => write!(f, "{err}"),
+            AlmanacError::UnknownCategory(category) => {
+                write!(f, "no map mentions the category `{category}`")
+            }
+            AlmanacError::BrokenChain {
+                from,
+                to,
+                dead_ends,
+            } => write!(
+                f,
+                "no chain of maps leads from `{from}` to `{to}`, it stops at `{}`",
+                dead_ends.join("`, `")
+            ),
+        }
+    }
+}
+
+impl<T> From<InvertError<T>> for AlmanacError<T> {
+    fn from(err: InvertError<T>) -> Self {
+        AlmanacError::NotInvertible(err)
+    }
+}
+
+/// Why a [`Map`] has no well defined inverse: `values` are reached from
+/// more than one place, one for each of `preimages`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvertError<T = u64> {
+    pub map: String,
+    pub values: Range<T>,
+    pub preimages: Vec<Range<T>>,
+}
+
+impl<T: fmt::Debug> fmt::Display for InvertError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:?} is reached from each of {:?}",
+            self.map, self.values, self.preimages
+        )
+    }
+}
+
+/// Two rows of a [`Map`] whose source ranges share `values`, so it is
+/// ambiguous where those values go.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Overlap<T = u64> {
+    pub lines: (Option<usize>, Option<usize>),
+    pub values: Range<T>,
+}
+
+impl<T: fmt::Debug> fmt::Display for Overlap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |line: Option<usize>| line.map_or("?".to_string(), |l| l.to_string());
+        write!(
+            f,
+            "rows on lines {} and {} both map {:?}",
+            line(self.lines.0),
+            line(self.lines.1),
+            self.values
+        )
+    }
+}
+
+/// Every [`Overlap`] found in a [`Map`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct ValidationError<T = u64> {
+    pub map: String,
+    pub overlaps: Vec<Overlap<T>>,
+}
+
+impl<T: fmt::Debug> fmt::Display for ValidationError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.map, self.overlaps.iter().join(", "))
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for ValidationError<T> {}
+
+/// Splits a `seed-to-soil map` header into its source and destination
+/// categories.
+pub fn categories(header: &str) -> Option<(String, String)> {
+    let header = header.trim().trim_end_matches(':');
+    let (from, to) = header.strip_suffix(" map")?.split_once("-to-")?;
+    Some((from.to_string(), to.to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct Map<T = u64> {
+    pub from: String,
+    pub to: String,
+    pub ranges: Vec<MapRange<T>>,
+}
+
+impl<T: Value> Map<T> {
+    pub fn map_to_next(&self, seed: T) -> T {
+        match self.ranges.iter().find(|r| r.contains(&seed)) {
+            Some(range) => range.translate(seed),
+            None => seed,
+        }
+    }
+
+    /// Splits `seeds` at every range boundary it crosses, pairing each piece
+    /// with where it lands. Pieces that fall between ranges map to themselves.
+    pub fn split_range(&self, seeds: &Range<T>) -> Vec<(Range<T>, Range<T>)> {
+        let mut pieces = Vec::new();
+        let mut start = seeds.start;
+
+        while start < seeds.end {
+            let (end, dest_start) = match self.ranges.iter().find(|r| r.contains(&start)) {
+                Some(range) => (range.src.end.min(seeds.end), range.translate(start)),
+                None => {
+                    let next_range = self
+                        .ranges
+                        .iter()
+                        .map(|r| r.src.start)
+                        .filter(|&s| s > start)
+                        .min()
+                        .unwrap_or(seeds.end);
+                    (next_range.min(seeds.end), start)
+                }
+            };
+
+            let length = end.wrapping_sub(start);
+            pieces.push((start..end, dest_start..dest_start.wrapping_add(length)));
+            start = end;
+        }
+
+        pieces
+    }
+
+    /// The reverse mapping, from `to` back to `from`.
+    ///
+    /// Values outside every range map to themselves in both directions, so
+    /// this only works if no two destination ranges overlap and every
+    /// destination value is also covered by a source range. Together those
+    /// mean the ranges shuffle one set of values among themselves.
+    pub fn invert(&self) -> Result<Map<T>, InvertError<T>> {
+        let map = format!("{}-to-{}", self.from, self.to);
+
+        let by_dest = self
+            .ranges
+            .iter()
+            .sorted_by_key(|r| r.dest.start)
+            .collect::<Vec<_>>();
+
+        for (a, b) in by_dest.iter().tuple_windows() {
+            if a.dest.end > b.dest.start {
+                let values = b.dest.start..a.dest.end.min(b.dest.end);
+                return Err(InvertError {
+                    map,
+                    preimages: vec![a.preimage(&values), b.preimage(&values)],
+                    values,
+                });
+            }
+        }
+
+        let src = union(self.ranges.iter().map(|r| r.src.clone()));
+        let dest = union(self.ranges.iter().map(|r| r.dest.clone()));
+
+        if let Some(uncovered) = difference(&dest, &src).first() {
+            let range = by_dest
+                .iter()
+                .find(|r| r.dest.start < uncovered.end && uncovered.start < r.dest.end)
+                .unwrap();
+            let values = uncovered.start.max(range.dest.start)..uncovered.end.min(range.dest.end);
+
+            return Err(InvertError {
+                map,
+                preimages: vec![range.preimage(&values), values.clone()],
+                values,
+            });
+        }
+
+        Ok(Map {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            ranges: self
+                .ranges
+                .iter()
+                .map(|r| MapRange {
+                    src: r.dest.clone(),
+                    dest: r.src.clone(),
+                    line: r.line,
+                })
+                .sorted_by_key(|r| r.src.start)
+                .collect(),
+        })
+    }
+
+    /// Checks that no value is covered by more than one row.
+    pub fn validate(&self) -> Result<(), ValidationError<T>> {
+        let overlaps = self
+            .ranges
+            .iter()
+            .tuple_combinations()
+            .filter_map(|(a, b)| a.overlap(b))
+            .collect::<Vec<_>>();
+
+        match overlaps.is_empty() {
+            true => Ok(()),
+            false => Err(ValidationError {
+                map: format!("{}-to-{}", self.from, self.to),
+                overlaps,
+            }),
+        }
+    }
+
+    /// Fills every gap with a range that maps to itself, including before
+    /// the first range and after the last, so the ranges cover
+    /// `T::MIN..T::MAX` without holes. `T::MAX` itself can't be in a
+    /// half-open range and is left out.
+    ///
+    /// Rows that overlap would map some values twice, so they're rejected
+    /// as [`Map::validate`] does.
+    pub fn normalise(self) -> Result<Self, ValidationError<T>> {
+        self.validate()?;
+        let map = self.with_implicit_empty_ranges();
+
+        let first = map.ranges.first().map_or(T::MAX, |r| r.src.start);
+        let last = map.ranges.iter().map(|r| r.src.end).max().unwrap_or(T::MAX);
+
+        let edges = [T::MIN..first, last..T::MAX]
+            .into_iter()
+            .filter(|range| range.start < range.end)
+            .map(|range| MapRange {
+                src: range.clone(),
+                dest: range,
+                line: None,
+            });
+
+        Ok(Self {
+            ranges: map
+                .ranges
+                .into_iter()
+                .chain(edges)
+                .sorted_by_key(|r| r.src.start)
+                .collect(),
+            ..map
+        })
+    }
+
+    pub fn with_implicit_empty_ranges(self) -> Self {
+        let mut empty_ranges = Vec::new();
+
+        for (a, b) in self.ranges.iter().tuple_windows() {
+            if b.src.start > a.src.end {
+                empty_ranges.push(MapRange {
+                    src: a.src.end..b.src.start,
+                    dest: a.src.end..b.src.start,
+                    line: None,
+                })
+            }
+        }
+
+        Self {
+            ranges: self
+                .ranges
+                .into_iter()
+                .chain(empty_ranges)
+                .sorted_by(|a, b| Ord::cmp(&a.src.start, &b.src.start))
+                .collect(),
+            ..self
+        }
+    }
+}
+
+impl<T: Value> FromStr for Map<T> {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().zip(1..).peekable();
+
+        let (from, to) = match lines.next_if(|(line, _)| line.trim().ends_with("map:")) {
+            Some((header, _)) => {
+                categories(header).ok_or("expected a `<from>-to-<to> map:` header")?
+            }
+            None => (String::new(), String::new()),
+        };
+
+        let mut ranges = lines
+            .map(|(line, number)| {
+                line.parse::<MapRange<T>>().map(|range| MapRange {
+                    line: Some(number),
+                    ..range
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        ranges.sort_by_key(|range| range.src.start);
+
+        Ok(Map { from, to, ranges })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapRange<T = u64> {
+    pub src: Range<T>,
+    pub dest: Range<T>,
+    /// The line the row was parsed from, if it wasn't made up to fill a gap.
+    pub line: Option<usize>,
+}
+
+impl<T: Value> MapRange<T> {
+    /// The part of `src` that lands on `values`, which must lie within `dest`.
+    fn preimage(&self, values: &Range<T>) -> Range<T> {
+        let start = self
+            .src
+            .start
+            .wrapping_add(values.start.wrapping_sub(self.dest.start));
+        start..start.wrapping_add(values.end.wrapping_sub(values.start))
+    }
+
+    pub fn contains(&self, seed: &T) -> bool {
+        self.src.contains(seed)
+    }
+
+    /// The values both rows claim to map, if there are any.
+    pub fn overlap(&self, other: &Self) -> Option<Overlap<T>> {
+        (self.src.start < other.src.end && other.src.start < self.src.end).then(|| Overlap {
+            lines: (self.line, other.line),
+            values: self.src.start.max(other.src.start)..self.src.end.min(other.src.end),
+        })
+    }
+
+    pub fn translate(&self, seed: T) -> T {
+        if self.contains(&seed) {
+            let diff = seed.wrapping_sub(self.src.start);
+            self.dest.start.wrapping_add(diff)
+        } else {
+            seed
+        }
+    }
+
+    pub fn translate_range(&self, seed_range: &Range<T>) -> Option<Range<T>> {
+        if self.contains(&seed_range.start) {
+            let start = self.translate(seed_range.start);
+            let end = if self.contains(&seed_range.end) {
+                self.translate(seed_range.end)
+            } else {
+                self.dest.end
+            };
+
+            Some(start..end)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Value> FromStr for MapRange<T> {
+    type Err = &'static str;
+
+    /// Reads a `<dest> <src> <length>` row, without a line number.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(|_| "invalid number in range")?;
+
+        let [dest_start, src_start, length] = numbers[..] else {
+            return Err("expected three numbers per range");
+        };
+        if length < T::ZERO {
+            return Err("negative range length");
+        }
+
+        let end = |start: T| {
+            start
+                .checked_add(length)
+                .ok_or("range runs past the largest value")
+        };
+
+        Ok(MapRange {
+            src: src_start..end(src_start)?,
+            dest: dest_start..end(dest_start)?,
+            line: None,
+        })
+    }
+}
+
+/// Merges ranges into a sorted list of disjoint ranges.
+fn union<T: Value>(ranges: impl IntoIterator<Item = Range<T>>) -> Vec<Range<T>> {
+    ranges
+        .into_iter()
+        .filter(|r| r.start < r.end)
+        .sorted_by_key(|r| r.start)
+        .fold(Vec::new(), |mut merged: Vec<Range<T>>, range| {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+            merged
+        })
+}
+
+/// The parts of `a` not covered by `b`.
+fn difference<T: Value>(a: &[Range<T>], b: &[Range<T>]) -> Vec<Range<T>> {
+    a.iter()
+        .flat_map(|range| {
+            b.iter().fold(vec![range.clone()], |pieces, cut| {
+                pieces
+                    .into_iter()
+                    .flat_map(|p| [p.start..p.end.min(cut.start), p.start.max(cut.end)..p.end])
+                    .filter(|p| p.start < p.end)
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let input = "50 98 2\n52 50 48";
+
+        let map = input.parse::<Map>().unwrap();
+
+        assert_eq!(map.map_to_next(98), 50);
+        assert_eq!(map.map_to_next(99), 51);
+        assert_eq!(map.map_to_next(10), 10);
+        assert_eq!(map.map_to_next(50), 52);
+        assert_eq!(map.map_to_next(53), 55);
+    }
+
+    #[test]
+    fn test_map_categories() {
+        let map = "seed-to-soil map:\n50 98 2".parse::<Map>().unwrap();
+        assert_eq!((map.from.as_str(), map.to.as_str()), ("seed", "soil"));
+
+        assert_eq!(
+            "seed-soil map:\n50 98 2".parse::<Map>().unwrap_err(),
+            "expected a `<from>-to-<to> map:` header"
+        );
+    }
+
+    #[test]
+    fn test_almanac_path_out_of_order() {
+        let almanac = [
+            "light-to-temperature map:\n0 10 5",
+            "seed-to-soil map:\n100 0 5",
+            "water-to-light map:\n10 200 5",
+            "soil-to-water map:\n200 100 5",
+        ]
+        .iter()
+        .map(|str| str.parse().unwrap())
+        .collect::<Almanac>();
+
+        let path = almanac
+            .path("seed", "temperature")
+            .unwrap()
+            .iter()
+            .map(|map| map.to.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(path, vec!["soil", "water", "light", "temperature"]);
+
+        assert_eq!(almanac.convert("seed", "temperature", 3), Ok(3));
+        assert_eq!(almanac.convert("soil", "light", 101), Ok(11));
+        assert_eq!(almanac.convert("soil", "soil", 101), Ok(101));
+
+        let chain = almanac.chain("water", "temperature").unwrap();
+        assert_eq!(chain.maps.len(), 2);
+        assert_eq!(chain.maps[0].from, "water");
+    }
+
+    #[test]
+    fn test_almanac_broken_chain() {
+        let almanac = [
+            "seed-to-soil map:\n100 0 5",
+            "water-to-light map:\n10 200 5",
+        ]
+        .iter()
+        .map(|str| str.parse().unwrap())
+        .collect::<Almanac>();
+
+        assert_eq!(
+            almanac.path("seed", "light").unwrap_err(),
+            AlmanacError::BrokenChain {
+                from: "seed".to_string(),
+                to: "light".to_string(),
+                dead_ends: vec!["soil".to_string()],
+            }
+        );
+        assert_eq!(
+            almanac.path("seed", "humidity").unwrap_err(),
+            AlmanacError::UnknownCategory("humidity".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invert_map() {
+        let map = "seed-to-soil map:\n50 98 2\n52 50 48"
+            .parse::<Map>()
+            .unwrap();
+        let inverse = map.invert().unwrap();
+
+        assert_eq!(
+            (inverse.from.as_str(), inverse.to.as_str()),
+            ("soil", "seed")
+        );
+        for seed in [0, 49, 50, 79, 97, 98, 99, 100, 1000] {
+            assert_eq!(inverse.map_to_next(map.map_to_next(seed)), seed);
+        }
+    }
+
+    #[test]
+    fn test_invert_reports_ambiguity() {
+        let map = "a-to-b map:\n0 10 5\n3 20 5".parse::<Map>().unwrap();
+        assert_eq!(
+            map.invert().unwrap_err(),
+            InvertError {
+                map: "a-to-b".to_string(),
+                values: 3..5,
+                preimages: vec![13..15, 20..22],
+            }
+        );
+
+        let map = "a-to-b map:\n0 10 5".parse::<Map>().unwrap();
+        assert_eq!(
+            map.invert().unwrap_err(),
+            InvertError {
+                map: "a-to-b".to_string(),
+                values: 0..5,
+                preimages: vec![10..15, 0..5],
+            }
+        );
+    }
+
+    #[test]
+    fn test_almanac_preimage() {
+        let almanac = [
+            "seed-to-soil map:\n50 98 2\n52 50 48",
+            "soil-to-fertilizer map:\n0 15 37\n37 52 2\n39 0 15",
+        ]
+        .iter()
+        .map(|str| str.parse().unwrap())
+        .collect::<Almanac>();
+
+        let seeds = almanac.preimage("seed", "fertilizer", &(0..10)).unwrap();
+        assert_eq!(seeds, vec![15..25]);
+
+        for range in almanac.preimage("seed", "fertilizer", &(35..45)).unwrap() {
+            for seed in range {
+                let fertilizer = almanac.convert("seed", "fertilizer", seed).unwrap();
+                assert!((35..45).contains(&fertilizer));
+            }
+        }
+
+        let inverse = almanac.invert().unwrap();
+        assert_eq!(inverse.convert("fertilizer", "seed", 81), Ok(79));
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        assert_eq!(
+            "50 98 2\n52 50".parse::<Map>().unwrap_err(),
+            "expected three numbers per range"
+        );
+        assert_eq!(
+            "50 98 2\n52 fifty 48".parse::<Map>().unwrap_err(),
+            "invalid number in range"
+        );
+        assert_eq!(
+            "0 18446744073709551610 10".parse::<Map>().unwrap_err(),
+            "range runs past the largest value"
+        );
+    }
+
+    #[test]
+    fn test_split_range() {
+        let input = "50 98 2\n52 50 38";
+        let map = input.parse::<Map>().unwrap();
+
+        let expected = vec![
+            (40..50, 40..50),
+            (50..88, 52..90),
+            (88..98, 88..98),
+            (98..100, 50..52),
+            (100..105, 100..105),
+        ];
+
+        assert_eq!(map.split_range(&(40..105)), expected);
+        assert_eq!(map.split_range(&(60..70)), vec![(60..70, 62..72)]);
+        assert_eq!(map.split_range(&(70..70)), vec![]);
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let input = "50 98 2\n52 50 38";
+        let expected = Map {
+            from: String::new(),
+            to: String::new(),
+            ranges: vec![
+                MapRange {
+                    src: 50..88,
+                    dest: 52..90,
+                    line: Some(2),
+                },
+                MapRange {
+                    src: 88..98,
+                    dest: 88..98,
+                    line: None,
+                },
+                MapRange {
+                    src: 98..100,
+                    dest: 50..52,
+                    line: Some(1),
+                },
+            ],
+        };
+        let map = input.parse::<Map>().unwrap().with_implicit_empty_ranges();
+
+        assert_eq!(map.ranges, expected.ranges);
+
+        let input = "50 98 2\n52 50 48";
+        let expected = Map {
+            from: String::new(),
+            to: String::new(),
+            ranges: vec![
+                MapRange {
+                    src: 50..98,
+                    dest: 52..100,
+                    line: Some(2),
+                },
+                MapRange {
+                    src: 98..100,
+                    dest: 50..52,
+                    line: Some(1),
+                },
+            ],
+        };
+        let map = input.parse::<Map>().unwrap().with_implicit_empty_ranges();
+
+        assert_eq!(map.ranges, expected.ranges);
+    }
+
+    #[test]
+    fn test_validate_overlaps() {
+        let map = "seed-to-soil map:\n50 98 2\n52 50 48"
+            .parse::<Map>()
+            .unwrap();
+        assert_eq!(map.validate(), Ok(()));
+
+        // Touching ranges share no values.
+        let map = "0 10 5\n100 15 5".parse::<Map>().unwrap();
+        assert_eq!(map.validate(), Ok(()));
+
+        let map = "a-to-b map:\n0 10 5\n100 14 5\n200 0 100"
+            .parse::<Map>()
+            .unwrap();
+        let err = map.validate().unwrap_err();
+        assert_eq!(
+            err.overlaps,
+            vec![
+                Overlap {
+                    lines: (Some(4), Some(2)),
+                    values: 10..15,
+                },
+                Overlap {
+                    lines: (Some(4), Some(3)),
+                    values: 14..19,
+                },
+                Overlap {
+                    lines: (Some(2), Some(3)),
+                    values: 14..15,
+                },
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "a-to-b: rows on lines 4 and 2 both map 10..15, \
+             rows on lines 4 and 3 both map 14..19, \
+             rows on lines 2 and 3 both map 14..15"
+        );
+    }
+
+    #[test]
+    fn test_normalise() {
+        let srcs = |map: &Map| map.ranges.iter().map(|r| r.src.clone()).collect::<Vec<_>>();
+
+        let map = "50 98 2\n52 50 38"
+            .parse::<Map>()
+            .unwrap()
+            .normalise()
+            .unwrap();
+        assert_eq!(
+            srcs(&map),
+            vec![0..50, 50..88, 88..98, 98..100, 100..u64::MAX]
+        );
+        assert_eq!(map.map_to_next(0), 0);
+        assert_eq!(map.map_to_next(u64::MAX - 1), u64::MAX - 1);
+
+        // Nothing to add at either edge.
+        let max = u64::MAX;
+        let map = format!("10 0 5\n0 {} 5", max - 5)
+            .parse::<Map>()
+            .unwrap()
+            .normalise()
+            .unwrap();
+        assert_eq!(srcs(&map), vec![0..5, 5..max - 5, max - 5..max]);
+        assert_eq!(map.map_to_next(max - 1), 4);
+
+        let map = "".parse::<Map>().unwrap().normalise().unwrap();
+        assert_eq!(srcs(&map), vec![0..u64::MAX]);
+
+        // Overlapping rows would map some values twice.
+        let map = "a-to-b map:\n0 10 10\n100 15 10".parse::<Map>().unwrap();
+        assert_eq!(map.clone().normalise().err(), map.validate().err());
+        assert_eq!(
+            map.normalise().unwrap_err().to_string(),
+            "a-to-b: rows on lines 2 and 3 both map 15..20"
+        );
+    }
+
+    #[test]
+    fn test_unsigned_limits() {
+        let max = u64::MAX;
+        let range = format!("0 {} 10", max - 10).parse::<MapRange>().unwrap();
+        assert_eq!(range.translate(max - 10), 0);
+        assert_eq!(range.translate(max - 1), 9);
+        // Half-open, so `MAX` itself is never in a range.
+        assert_eq!(range.translate(max), max);
+        assert_eq!(range.translate_range(&(max - 5..max)), Some(5..10));
+
+        let range = format!("{} 0 10", max - 10).parse::<MapRange>().unwrap();
+        assert_eq!(range.translate(9), max - 1);
+        assert_eq!(range.translate_range(&(0..10)), Some(max - 10..max));
+
+        let max = u32::MAX;
+        let map = format!("0 {} 5\n{} 0 5", max - 5, max - 5)
+            .parse::<Map<u32>>()
+            .unwrap();
+        assert_eq!(map.map_to_next(max - 1), 4);
+        assert_eq!(map.map_to_next(4), max - 1);
+        assert_eq!(map.invert().unwrap().map_to_next(max - 1), 4);
+
+        let max = u128::MAX;
+        let map = format!("1 {} 1", max - 1).parse::<Map<u128>>().unwrap();
+        assert_eq!(map.map_to_next(max - 1), 1);
+        assert_eq!(
+            map.split_range(&(max - 3..max)),
+            vec![(max - 3..max - 1, max - 3..max - 1), (max - 1..max, 1..2)]
+        );
+    }
+
+    #[test]
+    fn test_signed_limits() {
+        let (min, max) = (i64::MIN, i64::MAX);
+
+        // From the very top of the range to the very bottom, further than
+        // any i64 offset reaches.
+        let range = format!("{min} {} 10", max - 10)
+            .parse::<MapRange<i64>>()
+            .unwrap();
+        assert_eq!(range.translate(max - 10), min);
+        assert_eq!(range.translate(max - 1), min + 9);
+        assert_eq!(
+            range.translate_range(&(max - 4..max)),
+            Some(min + 6..min + 10)
+        );
+
+        let map = "-5 5 10\n5 -5 10".parse::<Map<i64>>().unwrap();
+        assert_eq!(map.map_to_next(-3), 7);
+        assert_eq!(map.map_to_next(7), -3);
+        assert_eq!(map.map_to_next(-6), -6);
+
+        // The gaps either side reach from MIN and up to MAX.
+        let map = map.normalise().unwrap();
+        assert_eq!(
+            map.ranges.iter().map(|r| r.src.clone()).collect::<Vec<_>>(),
+            vec![min..-5, -5..5, 5..15, 15..max]
+        );
+        assert_eq!(map.map_to_next(min), min);
+        assert_eq!(map.map_to_next(max - 1), max - 1);
+        assert_eq!(
+            map.split_range(&(min..max))[..2],
+            [(min..-5, min..-5), (-5..5, 5..15)]
+        );
+
+        assert_eq!(
+            "0 0 -1".parse::<Map<i64>>().unwrap_err(),
+            "negative range length"
+        );
+        assert_eq!(
+            format!("0 {} 2", max - 1).parse::<Map<i64>>().unwrap_err(),
+            "range runs past the largest value"
+        );
+    }
+}
@@ -0,0 +1,524 @@
+pub mod flow;
+pub mod map;
+
+use std::ops::Range;
+
+use flow::Flow;
+use map::{categories, Almanac, Map, MapRange};
+
+use crate::{
+    aoc,
+    arith::{self, Wide, Widen},
+    parsing,
+    sections::{SectionError, Sections},
+};
+use itertools::Itertools;
+
+pub fn parse(input: &str) -> Result<(Vec<u64>, Almanac), SectionError> {
+    let sections = Sections::parse(input);
+    sections.expect_prefix(&["seeds"])?;
+
+    let (seeds_section, maps) = sections.sections.split_first().unwrap();
+
+    let seeds = seeds_section
+        .lines
+        .iter()
+        .flat_map(|line| line.split_whitespace())
+        .map(|str| str.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| seeds_section.error("invalid seed number"))?;
+    if seeds.is_empty() {
+        return Err(seeds_section.error("expected at least one seed"));
+    }
+
+    let almanac = maps
+        .iter()
+        .map(|section| {
+            let (from, to) = section
+                .name
+                .and_then(categories)
+                .ok_or_else(|| section.error("expected a `<from>-to-<to> map` header"))?;
+
+            // Rows start on the line after the header. A row that doesn't
+            // parse, or that maps values an earlier row already does, fails
+            // the whole block when parsing strictly and is dropped otherwise.
+            let mut ranges = Vec::<MapRange>::new();
+            for (row, line) in section.lines.iter().zip(section.line + 1..) {
+                let range = row
+                    .parse::<MapRange>()
+                    .map_err(String::from)
+                    .and_then(|range| {
+                        let range = MapRange {
+                            line: Some(line),
+                            ..range
+                        };
+                        match ranges.iter().find_map(|kept| kept.overlap(&range)) {
+                            Some(overlap) => Err(format!("{from}-to-{to}: {overlap}")),
+                            None => Ok(range),
+                        }
+                    });
+                if let Some(range) = parsing::check(input, line, range) {
+                    ranges.push(range.map_err(|bad| section.error_at(bad.line, bad.reason))?);
+                }
+            }
+            ranges.sort_by_key(|range| range.src.start);
+
+            let map = Map { from, to, ranges };
+            Ok(map)
+        })
+        .collect::<Result<Almanac, _>>()?;
+
+    // Both parts walk every seed to a location, so a missing link is as
+    // much a problem with the input as a malformed row.
+    almanac
+        .path("seed", "location")
+        .map_err(|err| seeds_section.error(err.to_string()))?;
+
+    Ok((seeds, almanac))
+}
+
+/// Like [`parse`], with the seeds read as `start length` pairs.
+pub fn parse_seed_ranges(input: &str) -> Result<(Vec<Range<u64>>, Almanac), SectionError> {
+    let (seeds, almanac) = parse(input)?;
+    let error = |reason: String| Sections::parse(input).sections[0].error(reason);
+
+    if seeds.len() % 2 != 0 {
+        return Err(error(
+            "expected seeds in start and length pairs".to_string(),
+        ));
+    }
+    let seed_ranges = seeds
+        .into_iter()
+        .tuples()
+        .map(|(start, len)| arith::try_add("seed range", start, len).map(|end| start..end))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| error(err.to_string()))?;
+
+    Ok((seed_ranges, almanac))
+}
+
+pub fn trace_seed_ranges(input: &str) -> Flow {
+    let (seed_ranges, almanac) = parse_seed_ranges(input).expect("failed to parse input");
+    let path = almanac
+        .path("seed", "location")
+        .expect("parse checks seeds reach a location");
+    Flow::trace(&path, &seed_ranges)
+}
+
+#[aoc(day = 5, part = 1)]
+pub fn part1((seeds, almanac): &(Vec<u64>, Almanac)) -> Wide<u64> {
+    seeds
+        .iter()
+        .map(|&seed| {
+            almanac
+                .convert("seed", "location", seed)
+                .expect("parse checks seeds reach a location")
+        })
+        .min()
+        .expect("parse rejects an empty seed list")
+        .widen()
+}
+
+#[aoc(day = 5, part = 2, parse = parse_seed_ranges)]
+pub fn part2((seed_ranges, almanac): &(Vec<Range<u64>>, Almanac)) -> Wide<u64> {
+    forward_search(almanac, seed_ranges).widen()
+}
+
+/// Pushes each seed range through the maps a piece at a time, where each
+/// piece is the longest run of seeds every map moves by the same amount.
+fn forward_search(almanac: &Almanac, seed_ranges: &[Range<u64>]) -> u64 {
+    let mapper = almanac
+        .path("seed", "location")
+        .expect("parse checks seeds reach a location")
+        .into_iter()
+        .cloned()
+        .map(|map| {
+            map.normalise()
+                .expect("parse rejects or drops overlapping rows")
+        })
+        .collect::<Almanac>();
+
+    seed_ranges
+        .iter()
+        .cloned()
+        .fold(u64::MAX, |mut lowest, mut seed_range| {
+            while seed_range.end - seed_range.start > 0 {
+                let subrange =
+                    mapper
+                        .maps
+                        .iter()
+                        .fold(seed_range.start..seed_range.end, |seeds, map| {
+                            map.ranges
+                                .iter()
+                                .find(|r| r.contains(&seeds.start))
+                                .and_then(|range| range.translate_range(&seeds))
+                                .unwrap_or(seeds)
+                        });
+
+                let size = subrange.end - subrange.start;
+
+                seed_range.start += size;
+
+                if subrange.start < lowest {
+                    lowest = subrange.start;
+                }
+            }
+            lowest
+        })
+}
+
+/// Searches for the lowest location by asking which seeds land below
+/// it, narrowing in on the first location any of our seeds can reach.
+///
+/// Much slower than [`forward_search`], but it gets there a different way,
+/// so it's kept as a check on it.
+#[cfg(test)]
+fn reverse_search(almanac: &Almanac, seed_ranges: &[Range<u64>]) -> u64 {
+    let reachable = |end: u64| {
+        almanac
+            .preimage("seed", "location", &(0..end))
+            .unwrap()
+            .iter()
+            .any(|seeds| {
+                seed_ranges
+                    .iter()
+                    .any(|range| range.start < seeds.end && seeds.start < range.end)
+            })
+    };
+
+    let (mut low, mut high) = (0, u64::MAX);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match reachable(mid + 1) {
+            true => high = mid,
+            false => low = mid + 1,
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Solution;
+
+    const SAMPLE: &str = "
+        seeds: 79 14 55 13
+
+        seed-to-soil map:
+        50 98 2
+        52 50 48
+
+        soil-to-fertilizer map:
+        0 15 37
+        37 52 2
+        39 0 15
+
+        fertilizer-to-water map:
+        49 53 8
+        0 11 42
+        42 0 7
+        57 7 4
+
+        water-to-light map:
+        88 18 7
+        18 25 70
+
+        light-to-temperature map:
+        45 77 23
+        81 45 19
+        68 64 13
+
+        temperature-to-humidity map:
+        0 69 1
+        1 0 69
+
+        humidity-to-location map:
+        60 56 37
+        56 93 4
+    ";
+
+    #[test]
+    fn test_sample_input_part1() {
+        let input = "
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+
+            soil-to-fertilizer map:
+            0 15 37
+            37 52 2
+            39 0 15
+
+            fertilizer-to-water map:
+            49 53 8
+            0 11 42
+            42 0 7
+            57 7 4
+
+            water-to-light map:
+            88 18 7
+            18 25 70
+
+            light-to-temperature map:
+            45 77 23
+            81 45 19
+            68 64 13
+
+            temperature-to-humidity map:
+            0 69 1
+            1 0 69
+
+            humidity-to-location map:
+            60 56 37
+            56 93 4
+        ";
+
+        let expected = 35;
+
+        let solution = PartOne::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sample_input_part2() {
+        let input = "
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+
+            soil-to-fertilizer map:
+            0 15 37
+            37 52 2
+            39 0 15
+
+            fertilizer-to-water map:
+            49 53 8
+            0 11 42
+            42 0 7
+            57 7 4
+
+            water-to-light map:
+            88 18 7
+            18 25 70
+
+            light-to-temperature map:
+            45 77 23
+            81 45 19
+            68 64 13
+
+            temperature-to-humidity map:
+            0 69 1
+            1 0 69
+
+            humidity-to-location map:
+            60 56 37
+            56 93 4
+        ";
+
+        let expected = 46;
+
+        let solution = PartTwo::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_reverse_search() {
+        let input = "
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50 48
+
+            soil-to-fertilizer map:
+            0 15 37
+            37 52 2
+            39 0 15
+
+            fertilizer-to-water map:
+            49 53 8
+            0 11 42
+            42 0 7
+            57 7 4
+
+            water-to-light map:
+            88 18 7
+            18 25 70
+
+            light-to-temperature map:
+            45 77 23
+            81 45 19
+            68 64 13
+
+            temperature-to-humidity map:
+            0 69 1
+            1 0 69
+
+            humidity-to-location map:
+            60 56 37
+            56 93 4
+        ";
+
+        let (seeds, almanac) = parse(input).unwrap();
+        let seed_ranges = seeds
+            .into_iter()
+            .tuples()
+            .map(|(start, len)| start..start + len)
+            .collect::<Vec<_>>();
+
+        assert_eq!(reverse_search(&almanac, &seed_ranges), 46);
+    }
+
+    #[test]
+    fn test_seed_ranges() {
+        let (seed_ranges, _) = parse_seed_ranges(SAMPLE).unwrap();
+        assert_eq!(seed_ranges, [79..93, 55..68]);
+
+        let err = parse_seed_ranges(&SAMPLE.replace("55 13", "55")).unwrap_err();
+        assert_eq!(err.reason, "expected seeds in start and length pairs");
+
+        // Fine as two seeds, but not as a range.
+        let input = SAMPLE.replace("79 14 55 13", "18446744073709551615 1");
+        let solvers = crate::YEAR.solvers(5);
+        assert!((solvers[0].solve)(&input).is_ok());
+        assert_eq!(
+            (solvers[1].solve)(&input).unwrap_err(),
+            "section 1 `seeds` (line 2): seed range: 18446744073709551615 + 1 overflows u64"
+        );
+    }
+
+    #[test]
+    fn test_reverse_search_agrees_with_forward_search() {
+        let (_, almanac) = parse(SAMPLE).unwrap();
+        let cases: [&[Range<u64>]; 4] = [
+            &[79..93, 55..68],
+            &[0..50, 60..100],
+            &[90..91, 14..15],
+            &[0..1, 99..100],
+        ];
+
+        for seed_ranges in cases {
+            assert_eq!(
+                reverse_search(&almanac, seed_ranges),
+                forward_search(&almanac, seed_ranges),
+                "{seed_ranges:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_reports_broken_block() {
+        let input = "
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+
+            soil-to-fertilizer map:
+            0 15
+
+            fertilizer-to-water map:
+            49 53 8
+
+            water-to-light map:
+            88 18 7
+
+            light-to-temperature map:
+            45 77 23
+
+            temperature-to-humidity map:
+            0 69 1
+
+            humidity-to-location map:
+            60 56 37
+        ";
+
+        parsing::set_mode(parsing::Mode::Strict);
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.name.as_deref(), Some("soil-to-fertilizer map"));
+        assert_eq!(err.line, 8);
+        assert_eq!(err.reason, "expected three numbers per range");
+
+        let input = input.replace("seeds:", "plants:");
+        let err = parse(&input).unwrap_err();
+        assert_eq!(err.reason, "expected section `seeds`");
+
+        let input = input
+            .replace("plants:", "seeds:")
+            .replace("0 15\n", "0 15 37\n            37 20 5\n");
+        let err = parse(&input).unwrap_err();
+        assert_eq!(err.line, 9);
+        assert_eq!(
+            err.reason,
+            "soil-to-fertilizer: rows on lines 8 and 9 both map 20..25"
+        );
+    }
+
+    #[test]
+    fn test_parse_checks_seeds_reach_a_location() {
+        let input = "
+            seeds: 79 14
+
+            seed-to-soil map:
+            50 98 2
+
+            fertilizer-to-location map:
+            0 15 37
+        ";
+
+        let err = parse(input).unwrap_err();
+        assert_eq!(err.name.as_deref(), Some("seeds"));
+        assert_eq!(
+            err.reason,
+            "no chain of maps leads from `seed` to `location`, it stops at `soil`"
+        );
+        assert!((crate::YEAR.solvers(5)[0].solve)(input).is_err());
+
+        let err = parse(&input.replace("79 14", "")).unwrap_err();
+        assert_eq!(err.reason, "expected at least one seed");
+    }
+
+    #[test]
+    fn test_lenient_parse_skips_bad_rows() {
+        let input = "
+            seeds: 79 14 55 13
+
+            seed-to-soil map:
+            50 98 2
+            52 50
+            10 99 5
+
+            soil-to-location map:
+            0 15 37
+        ";
+
+        parsing::set_mode(parsing::Mode::Lenient);
+        parsing::take_warnings();
+        let (_, almanac) = parse(input).unwrap();
+        assert_eq!(almanac.convert("seed", "soil", 98), Ok(50));
+        assert_eq!(almanac.convert("seed", "soil", 50), Ok(50));
+
+        // The overlapping row comes second, so it's the one dropped.
+        assert_eq!(almanac.convert("seed", "soil", 99), Ok(51));
+        assert_eq!(almanac.convert("seed", "soil", 100), Ok(100));
+
+        let skipped = parsing::take_warnings();
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[0].line, 6);
+        assert_eq!(skipped[0].text, "52 50");
+        assert!(skipped[0]
+            .reason
+            .ends_with("expected three numbers per range"));
+        assert_eq!(skipped[1].line, 7);
+        assert!(skipped[1]
+            .reason
+            .ends_with("seed-to-soil: rows on lines 5 and 7 both map 99..100"));
+    }
+}
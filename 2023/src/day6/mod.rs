@@ -0,0 +1,136 @@
+pub mod race;
+
+use crate::{
+    aoc,
+    arith::{self, Wide, Widen},
+};
+use itertools::Itertools;
+use race::Race;
+
+/// The races laid out in columns, one per time and distance pair.
+pub fn parse(input: &str) -> Result<Vec<Race>, &'static str> {
+    let (times, distances) = input.trim().split_once('\n').ok_or("expected two lines")?;
+    let times = times
+        .trim()
+        .strip_prefix("Time:")
+        .and_then(|str| {
+            str.split_whitespace()
+                .map(|str| str.parse().ok())
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or("failed to parse times")?;
+
+    let distances = distances
+        .trim()
+        .strip_prefix("Distance:")
+        .and_then(|str| {
+            str.split_whitespace()
+                .map(|str| str.parse().ok())
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or("failed to parse distances")?;
+
+    if times.len() != distances.len() {
+        return Err("expected as many distances as times");
+    }
+
+    Ok(times
+        .into_iter()
+        .zip_eq(distances)
+        .map(Race::from)
+        .collect())
+}
+
+/// The same sheet read as one long race, ignoring the spaces between the
+/// digits.
+pub fn parse_single_race(input: &str) -> Result<Race, &'static str> {
+    let (time, distance) = input.trim().split_once('\n').ok_or("expected two lines")?;
+    let time = time
+        .trim()
+        .strip_prefix("Time:")
+        .and_then(|str| str.split_whitespace().join("").parse().ok())
+        .ok_or("failed to parse time")?;
+
+    let distance = distance
+        .trim()
+        .strip_prefix("Distance:")
+        .and_then(|str| str.split_whitespace().join("").parse().ok())
+        .ok_or("failed to parse distance")?;
+
+    Ok(Race::from((time, distance)))
+}
+
+#[aoc(day = 6, part = 1)]
+pub fn part1(races: &[Race]) -> Wide<u64> {
+    let ways = races.iter().map(|race| race.num_ways_to_win().widen());
+    arith::product("product of ways to win", ways)
+}
+
+#[aoc(day = 6, part = 2, parse = parse_single_race)]
+pub fn part2(race: &Race) -> Wide<u64> {
+    race.num_ways_to_win().widen()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{normalise::Normalise, Solution};
+
+    #[test]
+    fn test_sample_input_part1() {
+        let input = "Time: 7 15 30\nDistance: 9 40 200";
+        let expected = 288;
+        let solution = PartOne::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_sample_input_part2() {
+        let input = "Time: 7 15 30\nDistance: 9 40 200";
+        let expected = 71503;
+        let solution = PartTwo::new(input).solve();
+
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_single_race_parse_errors() {
+        assert_eq!(
+            parse_single_race("Time: 7 15").err(),
+            Some("expected two lines")
+        );
+        assert_eq!(
+            parse_single_race("Time: x\nDistance: 9").err(),
+            Some("failed to parse time")
+        );
+        assert_eq!(
+            parse_single_race("Time: 7\nDistances: 9").err(),
+            Some("failed to parse distance")
+        );
+    }
+
+    #[test]
+    fn test_indented_windows_input() {
+        let input = "\u{feff}
+            Time:      7  15   30\r
+            Distance:  9  40  200\r
+        ";
+        let input = Normalise::new().with_dedent().apply(input).text;
+
+        assert_eq!(PartOne::new(&input).solve(), 288);
+        assert_eq!(PartTwo::new(&input).solve(), 71503);
+    }
+
+    #[test]
+    fn test_indented_input_without_dedent() {
+        let input = "
+            Time:      7  15   30\r
+            Distance:  9  40  200\r
+        ";
+        let input = Normalise::new().apply(input).text;
+
+        assert_eq!(PartOne::new(&input).solve(), 288);
+        assert_eq!(PartTwo::new(&input).solve(), 71503);
+    }
+}
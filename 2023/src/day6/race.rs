@@ -0,0 +1,275 @@
+use std::{fmt::Write, ops::RangeInclusive};
+
+/// How holding the button turns into speed. The puzzle's boat gains one
+/// millimetre per millisecond of speed for every millisecond held, with no
+/// limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Physics {
+    pub acceleration: u64,
+    pub max_speed: Option<u64>,
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Self {
+            acceleration: 1,
+            max_speed: None,
+        }
+    }
+}
+
+impl Physics {
+    pub fn speed(&self, hold_time: u64) -> u64 {
+        let speed = hold_time.saturating_mul(self.acceleration);
+        self.max_speed.map_or(speed, |max| speed.min(max))
+    }
+}
+
+pub struct Race {
+    time_allowed: u64,
+    distance_record: u64,
+    physics: Physics,
+}
+
+impl From<(u64, u64)> for Race {
+    fn from(value: (u64, u64)) -> Self {
+        Self {
+            time_allowed: value.0,
+            distance_record: value.1,
+            physics: Physics::default(),
+        }
+    }
+}
+
+impl Race {
+    pub fn with_physics(self, physics: Physics) -> Self {
+        Self { physics, ..self }
+    }
+
+    pub fn distance(&self, hold_time: u64) -> u64 {
+        let travel_time = self.time_allowed.saturating_sub(hold_time);
+        self.physics.speed(hold_time).saturating_mul(travel_time)
+    }
+
+    pub fn num_ways_to_win(&self) -> u64 {
+        self.winning_holds()
+            .map_or(0, |holds| holds.end() - holds.start() + 1)
+    }
+
+    /// The lowest hold time that goes furthest, and how far that is.
+    ///
+    /// Distance rises while holding adds more speed than it costs in travel
+    /// time and falls after, capped speed included, so the peak is the first
+    /// hold time the next one doesn't beat.
+    pub fn optimum(&self) -> (u64, u64) {
+        let hold_time = partition_point(0..self.time_allowed, |ht| {
+            self.distance(ht + 1) > self.distance(ht)
+        });
+        (hold_time, self.distance(hold_time))
+    }
+
+    /// The first and last hold times that beat the record. Every hold time
+    /// between them wins too, since distance only rises then falls.
+    pub fn winning_holds(&self) -> Option<RangeInclusive<u64>> {
+        let (peak, max_distance) = self.optimum();
+        if max_distance <= self.distance_record {
+            return None;
+        }
+
+        // Holding for the whole race goes nowhere, so the last winning hold
+        // is before `time_allowed` and the search can stop short of it,
+        // which keeps a race of `u64::MAX` from overflowing.
+        let first = partition_point(0..peak, |ht| self.distance(ht) <= self.distance_record);
+        let last = partition_point(peak..self.time_allowed, |ht| {
+            self.distance(ht) > self.distance_record
+        }) - 1;
+
+        Some(first..=last)
+    }
+
+    /// Distance against hold time, `width` columns across and `height` rows
+    /// up. Winning hold times are drawn with `#`, the rest with `+`, and the
+    /// record as a line of `-`.
+    pub fn chart(&self, width: usize, height: usize) -> String {
+        let columns = (self.time_allowed as usize)
+            .saturating_add(1)
+            .min(width)
+            .max(1);
+        let holds = (0..columns)
+            .map(|col| match columns {
+                1 => 0,
+                _ => (col as u128 * self.time_allowed as u128 / (columns as u128 - 1)) as u64,
+            })
+            .collect::<Vec<_>>();
+
+        let (_, max_distance) = self.optimum();
+        let top = max_distance.max(self.distance_record).max(1);
+        let row_of = |distance: u64| (distance as u128 * height as u128 / top as u128) as usize;
+        let record_row = row_of(self.distance_record);
+
+        let mut out = String::new();
+        writeln!(out, "{top:>12} ┤").unwrap();
+
+        for row in (1..=height).rev() {
+            let label = match row == record_row {
+                true => format!("{:>12} ┤", self.distance_record),
+                false => format!("{:>12} │", ""),
+            };
+            out.push_str(&label);
+
+            for &hold_time in &holds {
+                let distance = self.distance(hold_time);
+                let ch = match (row_of(distance) >= row, distance > self.distance_record) {
+                    (true, true) => '#',
+                    (true, false) => '+',
+                    _ if row == record_row => '-',
+                    _ => ' ',
+                };
+                out.push(ch);
+            }
+            out.push('\n');
+        }
+
+        writeln!(out, "{:>12} └{}", 0, "─".repeat(columns)).unwrap();
+        writeln!(
+            out,
+            "{:>15}{:<w$}{}",
+            0,
+            "",
+            self.time_allowed,
+            w = columns.saturating_sub(self.time_allowed.to_string().len() + 1)
+        )
+        .unwrap();
+
+        out
+    }
+}
+
+/// The first value in `range` where `pred` stops holding, assuming it holds
+/// for a prefix of the range and nowhere after.
+fn partition_point(range: std::ops::Range<u64>, pred: impl Fn(u64) -> bool) -> u64 {
+    let (mut low, mut high) = (range.start, range.end);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match pred(mid) {
+            true => low = mid + 1,
+            false => high = mid,
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_winning_holds() {
+        let race = Race::from((7, 9));
+        assert_eq!(race.winning_holds(), Some(2..=5));
+        assert_eq!(race.optimum(), (3, 12));
+
+        let race = Race::from((30, 200));
+        assert_eq!(race.winning_holds(), Some(11..=19));
+        assert_eq!(race.num_ways_to_win(), 9);
+        assert_eq!(race.optimum(), (15, 225));
+
+        let race = Race::from((30, 225));
+        assert_eq!(race.winning_holds(), None);
+        assert_eq!(race.num_ways_to_win(), 0);
+
+        let race = Race::from((0, 0));
+        assert_eq!(race.optimum(), (0, 0));
+        assert_eq!(race.winning_holds(), None);
+    }
+
+    #[test]
+    fn test_longest_race() {
+        let max = u64::MAX;
+
+        // Every hold but none and all of it goes somewhere.
+        let race = Race::from((max, 0));
+        assert_eq!(race.winning_holds(), Some(1..=max - 1));
+        assert_eq!(race.num_ways_to_win(), max - 1);
+
+        let race = Race::from((max, max));
+        assert_eq!(race.winning_holds(), None);
+        assert_eq!(race.num_ways_to_win(), 0);
+
+        let chart = Race::from((max, 0)).chart(20, 4);
+        assert!(chart.ends_with(&format!("{max}\n")));
+    }
+
+    #[test]
+    fn test_matches_linear_scan() {
+        let physics = [
+            Physics::default(),
+            Physics {
+                acceleration: 3,
+                max_speed: None,
+            },
+            Physics {
+                acceleration: 2,
+                max_speed: Some(9),
+            },
+            Physics {
+                acceleration: 1,
+                max_speed: Some(1),
+            },
+        ];
+
+        for physics in physics {
+            for time in 0..40 {
+                for record in [0, 5, 40, 100, 300] {
+                    let race = Race::from((time, record)).with_physics(physics);
+                    let wins = (0..=time)
+                        .filter(|&ht| race.distance(ht) > record)
+                        .collect::<Vec<_>>();
+                    let best = (0..=time).map(|ht| race.distance(ht)).max().unwrap();
+
+                    assert_eq!(race.num_ways_to_win(), wins.len() as u64);
+                    assert_eq!(
+                        race.winning_holds(),
+                        wins.first().map(|&first| first..=*wins.last().unwrap())
+                    );
+                    assert_eq!(race.optimum().1, best);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_capped_speed() {
+        let physics = Physics {
+            acceleration: 2,
+            max_speed: Some(6),
+        };
+        let race = Race::from((10, 30)).with_physics(physics);
+
+        assert_eq!(race.distance(2), 32);
+        assert_eq!(race.distance(3), 42);
+        assert_eq!(race.distance(5), 30);
+        assert_eq!(race.optimum(), (3, 42));
+        assert_eq!(race.winning_holds(), Some(2..=4));
+    }
+
+    #[test]
+    fn test_chart() {
+        let chart = Race::from((7, 9)).chart(80, 4);
+
+        let expected = [
+            "          12 ┤",
+            "             │   ##   ",
+            "           9 ┤--####--",
+            "             │ +####+ ",
+            "             │ +####+ ",
+            "           0 └────────",
+            "              0      7",
+        ];
+        assert_eq!(chart.lines().collect::<Vec<_>>(), expected);
+
+        // Long races are sampled down to the width asked for.
+        let chart = Race::from((1000, 0)).chart(50, 10);
+        assert!(chart.lines().all(|line| line.chars().count() <= 14 + 50));
+    }
+}
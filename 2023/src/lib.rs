@@ -1,16 +1,18 @@
-use std::fmt::Display;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
 
-pub trait Solution {
-    type Output: Display;
+pub use aoc_common::{
+    aoc, arith, iter, normalise, parsing, sections, show_notes, show_solutions, Solution,
+};
 
-    fn solve(&self) -> Self::Output;
-}
+use aoc_common::Year;
 
-pub fn show_solutions<P1, P2>(part1: P1, part2: P2)
-where
-    P1: Solution,
-    P2: Solution,
-{
-    println!("Part 1: {}", part1.solve());
-    println!("Part 2: {}", part2.solve());
-}
+pub const YEAR: Year = Year {
+    year: 2023,
+    package: env!("CARGO_PKG_NAME"),
+    manifest_dir: env!("CARGO_MANIFEST_DIR"),
+};
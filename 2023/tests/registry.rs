@@ -0,0 +1,38 @@
+use aoc_2023::{day6, Solution, YEAR};
+
+const RACES: &str = "Time: 7 15 30\nDistance: 9 40 200";
+
+#[test]
+fn test_every_day_is_registered() {
+    assert_eq!(YEAR.days(), (1..=6).collect::<Vec<_>>());
+
+    for day in YEAR.days() {
+        let parts = YEAR.solvers(day).iter().map(|s| s.part).collect::<Vec<_>>();
+        assert_eq!(parts, vec![1, 2], "day {day}");
+    }
+}
+
+#[test]
+fn test_registered_day_matches_module() {
+    let solvers = YEAR.solvers(6);
+
+    assert_eq!((solvers[0].solve)(RACES), Ok("288".to_string()));
+    assert_eq!(
+        (solvers[1].solve)(RACES),
+        Ok(day6::PartTwo::new(RACES).solve().to_string())
+    );
+
+    let races = day6::parse(RACES).unwrap();
+    assert_eq!(day6::part1(&races), 288);
+    assert_eq!(races[2].winning_holds(), Some(11..=19));
+}
+
+#[test]
+fn test_parse_errors_are_reported() {
+    let solvers = YEAR.solvers(6);
+
+    assert_eq!(
+        (solvers[0].solve)("Time: 7 15\nDistance: 9"),
+        Err("expected as many distances as times".to_string())
+    );
+}
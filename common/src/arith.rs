@@ -0,0 +1,291 @@
+use std::{
+    fmt,
+    ops::{Add, Mul, Rem, Sub},
+};
+
+/// An unsigned integer that answers are added and multiplied up in.
+pub trait Int:
+    Clone
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Rem<Output = Self>
+{
+    const NAME: &'static str;
+
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    fn checked_pow2(exp: u32) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($ty:ty),*) => {$(
+        impl Int for $ty {
+            const NAME: &'static str = stringify!($ty);
+
+            fn zero() -> Self {
+                0
+            }
+
+            fn one() -> Self {
+                1
+            }
+
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                <$ty>::checked_add(*self, *rhs)
+            }
+
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                <$ty>::checked_mul(*self, *rhs)
+            }
+
+            fn checked_pow2(exp: u32) -> Option<Self> {
+                <$ty>::checked_shl(1, exp)
+            }
+        }
+    )*};
+}
+
+impl_int!(u32, u64, u128);
+
+/// The type a solution's answer is reported in, given the type it counts
+/// in. That's the same type unless the `wide` feature widens it to `u128`
+/// or `bigint` lifts the limit altogether.
+pub trait Widen: Int {
+    type Wide: Int;
+
+    fn widen(self) -> Self::Wide;
+}
+
+pub type Wide<T> = <T as Widen>::Wide;
+
+macro_rules! impl_widen {
+    ($($ty:ty),*) => {$(
+        impl Widen for $ty {
+            #[cfg(not(any(feature = "wide", feature = "bigint")))]
+            type Wide = $ty;
+            #[cfg(all(feature = "wide", not(feature = "bigint")))]
+            type Wide = u128;
+            #[cfg(feature = "bigint")]
+            type Wide = Big;
+
+            fn widen(self) -> Self::Wide {
+                self.into()
+            }
+        }
+    )*};
+}
+
+impl_widen!(u32, u64);
+
+/// An arbitrary-precision answer, for the `bigint` feature.
+#[cfg(feature = "bigint")]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Big(pub num_bigint::BigUint);
+
+#[cfg(feature = "bigint")]
+mod big {
+    use super::{Big, Int};
+    use num_bigint::BigUint;
+    use std::{
+        fmt,
+        ops::{Add, Mul, Rem, Sub},
+    };
+
+    impl Int for Big {
+        const NAME: &'static str = "BigUint";
+
+        fn zero() -> Self {
+            Big(BigUint::ZERO)
+        }
+
+        fn one() -> Self {
+            Big(BigUint::from(1u8))
+        }
+
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            Some(Big(&self.0 + &rhs.0))
+        }
+
+        fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+            Some(Big(&self.0 * &rhs.0))
+        }
+
+        fn checked_pow2(exp: u32) -> Option<Self> {
+            Some(Big(BigUint::from(1u8) << exp))
+        }
+    }
+
+    impl Add for Big {
+        type Output = Big;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            Big(self.0 + rhs.0)
+        }
+    }
+
+    impl Sub for Big {
+        type Output = Big;
+
+        fn sub(self, rhs: Self) -> Self::Output {
+            Big(self.0 - rhs.0)
+        }
+    }
+
+    impl Mul for Big {
+        type Output = Big;
+
+        fn mul(self, rhs: Self) -> Self::Output {
+            Big(self.0 * rhs.0)
+        }
+    }
+
+    impl Rem for Big {
+        type Output = Big;
+
+        fn rem(self, rhs: Self) -> Self::Output {
+            Big(self.0 % rhs.0)
+        }
+    }
+
+    impl fmt::Display for Big {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    macro_rules! impl_from {
+        ($($ty:ty),*) => {$(
+            impl From<$ty> for Big {
+                fn from(value: $ty) -> Self {
+                    Big(BigUint::from(value))
+                }
+            }
+        )*};
+    }
+
+    impl_from!(u32, u64);
+
+    // Lets an answer be compared against a plain literal in tests.
+    impl PartialEq<u64> for Big {
+        fn eq(&self, other: &u64) -> bool {
+            self.0 == BigUint::from(*other)
+        }
+    }
+}
+
+/// Which operation overflowed, on what, and in which type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub what: &'static str,
+    pub operation: String,
+    pub ty: &'static str,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} overflows {}", self.what, self.operation, self.ty)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+fn overflow<T: Int>(what: &'static str, operation: String) -> Overflow {
+    Overflow {
+        what,
+        operation,
+        ty: T::NAME,
+    }
+}
+
+pub fn try_add<T: Int>(what: &'static str, a: T, b: T) -> Result<T, Overflow> {
+    a.checked_add(&b)
+        .ok_or_else(|| overflow::<T>(what, format!("{a} + {b}")))
+}
+
+pub fn try_mul<T: Int>(what: &'static str, a: T, b: T) -> Result<T, Overflow> {
+    a.checked_mul(&b)
+        .ok_or_else(|| overflow::<T>(what, format!("{a} * {b}")))
+}
+
+pub fn try_pow2<T: Int>(what: &'static str, exp: u32) -> Result<T, Overflow> {
+    T::checked_pow2(exp).ok_or_else(|| overflow::<T>(what, format!("2^{exp}")))
+}
+
+/// `a + b`, which with the `checked` feature panics naming `what` and the
+/// operands instead of wrapping or panicking anonymously.
+pub fn add<T: Int>(what: &'static str, a: T, b: T) -> T {
+    match cfg!(feature = "checked") {
+        true => try_add(what, a, b).unwrap_or_else(|err| panic!("{err}")),
+        false => a + b,
+    }
+}
+
+/// `a * b`, checked the same way as [`add`].
+pub fn mul<T: Int>(what: &'static str, a: T, b: T) -> T {
+    match cfg!(feature = "checked") {
+        true => try_mul(what, a, b).unwrap_or_else(|err| panic!("{err}")),
+        false => a * b,
+    }
+}
+
+/// `2^exp`, checked the same way as [`add`].
+pub fn pow2<T: Int>(what: &'static str, exp: u32) -> T {
+    match cfg!(feature = "checked") {
+        true => try_pow2(what, exp).unwrap_or_else(|err| panic!("{err}")),
+        false => T::checked_pow2(exp).expect("attempt to shift left with overflow"),
+    }
+}
+
+pub fn sum<T: Int>(what: &'static str, values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |total, value| add(what, total, value))
+}
+
+pub fn product<T: Int>(what: &'static str, values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::one(), |total, value| mul(what, total, value))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_checked_operations_name_the_overflow() {
+        assert_eq!(try_add("sum", 1u32, 2), Ok(3));
+        assert_eq!(
+            try_mul("gear ratio", 70_000u32, 70_000)
+                .unwrap_err()
+                .to_string(),
+            "gear ratio: 70000 * 70000 overflows u32"
+        );
+        assert_eq!(
+            try_add("total", u64::MAX, 1).unwrap_err().to_string(),
+            "total: 18446744073709551615 + 1 overflows u64"
+        );
+        assert_eq!(try_pow2::<u32>("score", 31), Ok(1 << 31));
+        assert!(try_pow2::<u32>("score", 32).is_err());
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        assert_eq!(sum("sum", [1u32, 2, 3]), 6);
+        assert_eq!(product("product", [2u64, 3, 4]), 24);
+        assert_eq!(pow2::<u128>("power", 100), 1 << 100);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    #[should_panic(expected = "power: 65536 * 65536 overflows u32")]
+    fn test_checked_feature_panics() {
+        product("power", [65_536u32, 65_536]);
+    }
+}
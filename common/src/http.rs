@@ -0,0 +1,239 @@
+//! Just enough of HTTP/1.1 to read a request, shared by the runner's API
+//! server and the stand-in for adventofcode.com the tests use.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+/// The longest request line or header line read.
+pub const MAX_LINE: usize = 8 << 10;
+
+/// The most bytes read before the body, request line included.
+pub const MAX_HEADERS: usize = 64 << 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Why a request couldn't be read, with the status to answer it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestError {
+    pub status: u16,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.reason)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// Reads one request from `stream`, with lines of at most [`MAX_LINE`]
+/// bytes, at most [`MAX_HEADERS`] bytes before the body and a body of at
+/// most `max_body` bytes sent with a `Content-Length`.
+pub fn read_request<S: Read>(stream: &mut S, max_body: usize) -> Result<Request, RequestError> {
+    let error = |status, reason| RequestError { status, reason };
+    // A read timeout shows up as one of these, depending on the platform.
+    let unreadable = |err: io::Error, reason| match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            error(408, "timed out reading the request")
+        }
+        _ => error(400, reason),
+    };
+    let mut reader = BufReader::new(stream);
+
+    let request_line = read_line(&mut reader, MAX_LINE)
+        .map_err(|err| unreadable(err, "unreadable request"))?
+        .ok_or(error(414, "request line is too long"))?;
+    let mut budget = MAX_HEADERS - request_line.len();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(error(400, "malformed request line"));
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(&mut reader, MAX_LINE.min(budget))
+            .map_err(|err| unreadable(err, "unreadable headers"))?
+            .ok_or(error(431, "headers are too large"))?;
+        budget -= line.len();
+        match line.trim_end().split_once(':') {
+            Some((key, value)) => headers.push((key.to_string(), value.trim().to_string())),
+            None => break,
+        }
+    }
+
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        headers,
+        body: String::new(),
+    };
+
+    // Bodies are only ever read by length, so a chunked one would be left
+    // in the stream and its chunk sizes taken for the next request.
+    if request.header("transfer-encoding").is_some() {
+        return Err(error(
+            501,
+            "only bodies with a Content-Length are supported",
+        ));
+    }
+    let length = match request.header("content-length") {
+        Some(length) => length
+            .parse()
+            .map_err(|_| error(400, "invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > max_body {
+        return Err(error(413, "body is too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| unreadable(err, "body is shorter than its Content-Length"))?;
+    request.body = String::from_utf8(body).map_err(|_| error(400, "body is not UTF-8"))?;
+
+    Ok(request)
+}
+
+/// Reads a line of at most `limit` bytes, newline included, or `None` if
+/// it goes on for longer.
+fn read_line(reader: &mut impl BufRead, limit: usize) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.by_ref().take(limit as u64).read_line(&mut line)?;
+    match line.len() == limit && !line.ends_with('\n') {
+        true => Ok(None),
+        false => Ok(Some(line)),
+    }
+}
+
+/// A stream that stops reading once its time is up, however the reads are
+/// spread out. A read timeout only limits each read, so without this a
+/// client sending a byte at a time could hold a request open for good.
+pub struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+impl<'a> Deadline<'a> {
+    pub fn new(stream: &'a TcpStream, timeout: Duration) -> Self {
+        Self {
+            stream,
+            until: Instant::now() + timeout,
+        }
+    }
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn read(request: &str) -> Result<Request, RequestError> {
+        read_request(&mut request.as_bytes(), 16)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request =
+            read("POST /2023/day/6 HTTP/1.1\r\nHost: x\r\nContent-Length: 5\r\n\r\nTime:").unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/2023/day/6");
+        assert_eq!(request.header("content-length"), Some("5"));
+        assert_eq!(request.body, "Time:");
+
+        let request = read("GET /2023 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(request.body, "");
+    }
+
+    #[test]
+    fn test_unreadable_requests() {
+        let status = |request| read(request).unwrap_err().status;
+
+        assert_eq!(status("\r\n"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: x\r\n\r\n"), 400);
+        assert_eq!(
+            status("POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\nshort"),
+            400
+        );
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 17\r\n\r\n"), 413);
+        assert_eq!(
+            status("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nTime:\r\n0\r\n\r\n"),
+            501
+        );
+    }
+
+    #[test]
+    fn test_header_limits() {
+        let status = |request: String| read(&request).unwrap_err().status;
+
+        let path = "x".repeat(MAX_LINE);
+        assert_eq!(status(format!("GET /{path} HTTP/1.1\r\n\r\n")), 414);
+
+        let value = "x".repeat(MAX_LINE);
+        assert_eq!(status(format!("GET / HTTP/1.1\r\nX: {value}\r\n\r\n")), 431);
+
+        let header = format!("X: {}\r\n", "x".repeat(1000));
+        let headers = header.repeat(MAX_HEADERS / header.len() + 1);
+        assert_eq!(status(format!("GET / HTTP/1.1\r\n{headers}\r\n")), 431);
+
+        // Just under both limits is fine.
+        let value = "x".repeat(MAX_LINE - "X: \r\n".len());
+        let request = read(&format!("GET / HTTP/1.1\r\nX: {value}\r\n\r\n")).unwrap();
+        assert_eq!(request.header("x"), Some(value.as_str()));
+    }
+
+    #[test]
+    fn test_deadline() {
+        use std::{io::Write, net::TcpListener, thread};
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        // A header a little at a time, each well inside any per-read
+        // timeout, never finishing the request.
+        let drip = thread::spawn(move || {
+            client.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+            for _ in 0..20 {
+                thread::sleep(Duration::from_millis(20));
+                if client.write_all(b"X").is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut stream = Deadline::new(&server, Duration::from_millis(100));
+        assert_eq!(read_request(&mut stream, 16).unwrap_err().status, 408);
+        drip.join().unwrap();
+    }
+}
@@ -0,0 +1,300 @@
+use crate::Year;
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_INPUT_DIR: &str = "puzzle-input";
+
+pub(crate) const USER_AGENT: &str = "github.com/ttdotsh/aoc";
+
+/// Puzzle inputs stored on disk as `dayN.txt`, downloaded on first use.
+///
+/// A day that already has an input file is never requested again, so the
+/// checked-in inputs keep working without a session token.
+pub struct InputCache {
+    year: u16,
+    dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl InputCache {
+    pub fn new(year: u16, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            year,
+            dir: dir.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+        }
+    }
+
+    /// Reads `AOC_INPUT_DIR`, `AOC_BASE_URL` and `AOC_SESSION`, falling back
+    /// to the year's own `puzzle-input` directory and the real site.
+    ///
+    /// `AOC_INPUT_DIR` is shared between years, so each one gets a
+    /// subdirectory of it.
+    pub fn from_env(year: &Year) -> Self {
+        let dir = match env::var("AOC_INPUT_DIR") {
+            Ok(dir) => Path::new(&dir).join(year.year.to_string()),
+            Err(_) => year.input_dir(),
+        };
+        let mut cache = Self::new(year.year, dir);
+
+        if let Ok(url) = env::var("AOC_BASE_URL") {
+            cache = cache.with_base_url(url);
+        }
+        if let Ok(session) = env::var("AOC_SESSION") {
+            cache = cache.with_session(session);
+        }
+
+        cache
+    }
+
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn session(&self) -> Option<&str> {
+        self.session.as_deref()
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}.txt"))
+    }
+
+    pub fn metadata_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}.meta"))
+    }
+
+    pub fn is_cached(&self, day: u8) -> bool {
+        self.path(day).is_file()
+    }
+
+    /// Returns the input for `day`, downloading and caching it if needed.
+    pub fn get(&self, day: u8) -> Result<String, FetchError> {
+        if self.is_cached(day) {
+            return Ok(fs::read_to_string(self.path(day))?);
+        }
+
+        let input = self.download(day)?;
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(day), &input)?;
+
+        let metadata = Metadata {
+            year: self.year,
+            day,
+            url: self.url(day),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since| since.as_secs())
+                .unwrap_or(0),
+            bytes: input.len(),
+        };
+        fs::write(self.metadata_path(day), metadata.to_string())?;
+
+        Ok(input)
+    }
+
+    pub fn metadata(&self, day: u8) -> Option<Metadata> {
+        fs::read_to_string(self.metadata_path(day))
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, self.year, day)
+    }
+
+    fn download(&self, day: u8) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+
+        let response = ureq::get(&self.url(day))
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", USER_AGENT)
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+/// What was fetched and when, stored next to the input as `dayN.meta`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Metadata {
+    pub year: u16,
+    pub day: u8,
+    pub url: String,
+    pub fetched_at: u64,
+    pub bytes: usize,
+}
+
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "year: {}", self.year)?;
+        writeln!(f, "day: {}", self.day)?;
+        writeln!(f, "url: {}", self.url)?;
+        writeln!(f, "fetched_at: {}", self.fetched_at)?;
+        writeln!(f, "bytes: {}", self.bytes)
+    }
+}
+
+impl std::str::FromStr for Metadata {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let field = |name: &str| {
+            s.lines()
+                .filter_map(|line| line.split_once(": "))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+                .ok_or("missing metadata field")
+        };
+
+        Ok(Metadata {
+            year: field("year")?.parse().map_err(|_| "invalid year")?,
+            day: field("day")?.parse().map_err(|_| "invalid day")?,
+            url: field("url")?.to_string(),
+            fetched_at: field("fetched_at")?
+                .parse()
+                .map_err(|_| "invalid timestamp")?,
+            bytes: field("bytes")?.parse().map_err(|_| "invalid size")?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    MissingSession,
+    Http { status: u16, body: String },
+    UnexpectedResponse(String),
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(f, "no session token, set AOC_SESSION"),
+            FetchError::Http { status, body } => {
+                write!(f, "server responded with {status}: {}", body.trim())
+            }
+            FetchError::UnexpectedResponse(body) => {
+                write!(f, "could not make sense of the response: {}", body.trim())
+            }
+            FetchError::Transport(err) => write!(f, "request failed: {err}"),
+            FetchError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(err: io::Error) -> Self {
+        FetchError::Io(err)
+    }
+}
+
+impl From<ureq::Error> for FetchError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => FetchError::Http {
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(transport) => FetchError::Transport(transport.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::MockServer;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/2023/day/5/input" => (200, "seeds: 1 2 3\n".to_string()),
+            _ => (404, "not found".to_string()),
+        });
+        let dir = temp_dir("fetch");
+        let cache = InputCache::new(2023, &dir)
+            .with_base_url(&server.url)
+            .with_session("abc123");
+
+        assert_eq!(cache.get(5).unwrap(), "seeds: 1 2 3\n");
+        assert_eq!(cache.get(5).unwrap(), "seeds: 1 2 3\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+
+        let metadata = cache.metadata(5).unwrap();
+        assert_eq!(metadata.year, 2023);
+        assert_eq!(metadata.day, 5);
+        assert_eq!(metadata.url, format!("{}/2023/day/5/input", server.url));
+        assert_eq!(metadata.bytes, 13);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cached_day_needs_no_session() {
+        let dir = temp_dir("cached");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "1abc2\n").unwrap();
+
+        let cache = InputCache::new(2023, &dir).with_base_url("http://127.0.0.1:9");
+
+        assert_eq!(cache.get(1).unwrap(), "1abc2\n");
+        assert!(matches!(cache.get(2), Err(FetchError::MissingSession)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_http_error_is_not_cached() {
+        let server = MockServer::start(|_| (400, "Please log in".to_string()));
+        let dir = temp_dir("error");
+        let cache = InputCache::new(2023, &dir)
+            .with_base_url(&server.url)
+            .with_session("expired");
+
+        match cache.get(3) {
+            Err(FetchError::Http { status, body }) => {
+                assert_eq!(status, 400);
+                assert_eq!(body, "Please log in");
+            }
+            other => panic!("expected an HTTP error, got {other:?}"),
+        }
+        assert!(!cache.is_cached(3));
+    }
+}
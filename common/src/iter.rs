@@ -0,0 +1,373 @@
+use std::iter::{Fuse, FusedIterator};
+
+/*
+* MaybeChain
+*/
+pub struct MaybeChain<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+}
+
+impl<A, B> Iterator for MaybeChain<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(it) = &mut self.a {
+            match it.next() {
+                None => self.a = None,
+                item => return item,
+            }
+        }
+
+        let item = self.b.as_mut()?.next();
+        if item.is_none() {
+            self.b = None;
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+        let (b_lower, b_upper) = self.b.as_ref().map_or((0, Some(0)), Iterator::size_hint);
+
+        let lower = a_lower.saturating_add(b_lower);
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+
+        (lower, upper)
+    }
+}
+
+impl<A, B> DoubleEndedIterator for MaybeChain<A, B>
+where
+    A: DoubleEndedIterator,
+    B: DoubleEndedIterator<Item = A::Item>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(it) = &mut self.b {
+            match it.next_back() {
+                None => self.b = None,
+                item => return item,
+            }
+        }
+
+        let item = self.a.as_mut()?.next_back();
+        if item.is_none() {
+            self.a = None;
+        }
+        item
+    }
+}
+
+// Each side is dropped the first time it runs dry, so neither is polled again.
+impl<A, B> FusedIterator for MaybeChain<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+}
+
+pub trait ChainOption: Iterator + Sized {
+    fn maybe_chain<I>(self, other: Option<I>) -> MaybeChain<Self, I>
+    where
+        I: Iterator<Item = Self::Item>,
+    {
+        MaybeChain {
+            a: Some(self),
+            b: other,
+        }
+    }
+}
+
+impl<I: Iterator> ChainOption for I {}
+
+/*
+* Neighbours
+*/
+/// Yields each element of a slice together with the elements either side of
+/// it, as `(before, current, after)`.
+pub struct Neighbours<'a, T> {
+    items: &'a [T],
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Neighbours<'a, T> {
+    fn window(&self, idx: usize) -> (Option<&'a T>, &'a T, Option<&'a T>) {
+        let before = idx.checked_sub(1).map(|i| &self.items[i]);
+        (before, &self.items[idx], self.items.get(idx + 1))
+    }
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = (Option<&'a T>, &'a T, Option<&'a T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.window(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Neighbours<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.window(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for Neighbours<'_, T> {}
+
+impl<T> FusedIterator for Neighbours<'_, T> {}
+
+pub trait WithNeighbours<T> {
+    fn neighbours(&self) -> Neighbours<'_, T>;
+}
+
+impl<T> WithNeighbours<T> for [T] {
+    fn neighbours(&self) -> Neighbours<'_, T> {
+        Neighbours {
+            items: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+}
+
+/*
+* Blocks
+*/
+/// Groups lines into the runs separated by blank lines, skipping the blank
+/// lines themselves.
+pub struct Blocks<I> {
+    lines: Fuse<I>,
+}
+
+impl<I> Iterator for Blocks<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block = Vec::new();
+
+        for line in self.lines.by_ref() {
+            match line.as_ref().trim().is_empty() {
+                true if block.is_empty() => continue,
+                true => break,
+                false => block.push(line),
+            }
+        }
+
+        (!block.is_empty()).then_some(block)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.lines.size_hint();
+        (0, upper.map(|lines| lines.div_ceil(2)))
+    }
+}
+
+impl<I> DoubleEndedIterator for Blocks<I>
+where
+    I: DoubleEndedIterator,
+    I::Item: AsRef<str>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let mut block = Vec::new();
+
+        while let Some(line) = self.lines.next_back() {
+            match line.as_ref().trim().is_empty() {
+                true if block.is_empty() => continue,
+                true => break,
+                false => block.push(line),
+            }
+        }
+
+        block.reverse();
+        (!block.is_empty()).then_some(block)
+    }
+}
+
+impl<I> FusedIterator for Blocks<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+}
+
+/*
+* Extensions
+*/
+pub trait IteratorExt: Iterator + Sized {
+    /// The first and last items, which are the same item when there is only
+    /// one.
+    fn first_and_last(mut self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Clone,
+    {
+        let first = self.next()?;
+        let last = self.fold(None, |_, item| Some(item));
+        let last = last.unwrap_or_else(|| first.clone());
+        Some((first, last))
+    }
+
+    fn blocks(self) -> Blocks<Self>
+    where
+        Self::Item: AsRef<str>,
+    {
+        Blocks { lines: self.fuse() }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+#[cfg(test)]
+mod test {
+    use std::vec::IntoIter;
+
+    use super::*;
+
+    #[test]
+    fn test_maybe_chain_with_some() {
+        let nums = vec![1, 2];
+        let more_nums = vec![3, 4];
+
+        let mut all_nums = nums.into_iter().maybe_chain(Some(more_nums.into_iter()));
+
+        assert_eq!(all_nums.next(), Some(1));
+        assert_eq!(all_nums.next(), Some(2));
+        assert_eq!(all_nums.next(), Some(3));
+        assert_eq!(all_nums.next(), Some(4));
+        assert_eq!(all_nums.next(), None);
+    }
+
+    #[test]
+    fn test_maybe_chain_with_none() {
+        let nums = vec![1, 2];
+
+        let mut all_nums = nums.into_iter().maybe_chain::<IntoIter<i32>>(None);
+
+        assert_eq!(all_nums.next(), Some(1));
+        assert_eq!(all_nums.next(), Some(2));
+        assert_eq!(all_nums.next(), None);
+    }
+
+    #[test]
+    fn test_maybe_chain_with_some_then_none() {
+        let nums = vec![1, 2];
+        let more_nums = vec![3, 4];
+
+        let mut all_nums = nums
+            .into_iter()
+            .maybe_chain(Some(more_nums.into_iter()))
+            .maybe_chain::<MaybeChain<IntoIter<i32>, IntoIter<i32>>>(None);
+
+        assert_eq!(all_nums.next(), Some(1));
+        assert_eq!(all_nums.next(), Some(2));
+        assert_eq!(all_nums.next(), Some(3));
+        assert_eq!(all_nums.next(), Some(4));
+        assert_eq!(all_nums.next(), None);
+    }
+
+    #[test]
+    fn test_maybe_chain_with_none_then_some() {
+        let nums = vec![1, 2];
+        let more_nums = vec![3, 4];
+
+        let mut all_nums = nums
+            .into_iter()
+            .maybe_chain::<IntoIter<i32>>(None)
+            .maybe_chain(Some(more_nums.into_iter()));
+
+        assert_eq!(all_nums.next(), Some(1));
+        assert_eq!(all_nums.next(), Some(2));
+        assert_eq!(all_nums.next(), Some(3));
+        assert_eq!(all_nums.next(), Some(4));
+        assert_eq!(all_nums.next(), None);
+    }
+
+    #[test]
+    fn test_maybe_chain_from_both_ends() {
+        let nums = vec![1, 2];
+        let more_nums = vec![3, 4];
+
+        let mut all_nums = nums.into_iter().maybe_chain(Some(more_nums.into_iter()));
+
+        assert_eq!(all_nums.size_hint(), (4, Some(4)));
+        assert_eq!(all_nums.next_back(), Some(4));
+        assert_eq!(all_nums.next(), Some(1));
+        assert_eq!(all_nums.next_back(), Some(3));
+        assert_eq!(all_nums.next_back(), Some(2));
+        assert_eq!(all_nums.size_hint(), (0, Some(0)));
+        assert_eq!(all_nums.next(), None);
+        assert_eq!(all_nums.next_back(), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let lines = ["a", "b", "c"];
+        let mut windows = lines.neighbours();
+
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows.next(), Some((None, &"a", Some(&"b"))));
+        assert_eq!(windows.next_back(), Some((Some(&"b"), &"c", None)));
+        assert_eq!(windows.next(), Some((Some(&"a"), &"b", Some(&"c"))));
+        assert_eq!(windows.next(), None);
+        assert_eq!(windows.next_back(), None);
+
+        let single = [1];
+        assert_eq!(
+            single.neighbours().collect::<Vec<_>>(),
+            vec![(None, &1, None)]
+        );
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        assert_eq!("a1b2c3".chars().first_and_last(), Some(('a', '3')));
+        assert_eq!("7".chars().first_and_last(), Some(('7', '7')));
+        assert_eq!("".chars().first_and_last(), None);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "\nseeds: 1 2\n\na map:\n1 2 3\n\n\nb map:\n4 5 6\n";
+
+        let blocks = input.lines().blocks().collect::<Vec<_>>();
+        assert_eq!(
+            blocks,
+            vec![
+                vec!["seeds: 1 2"],
+                vec!["a map:", "1 2 3"],
+                vec!["b map:", "4 5 6"],
+            ]
+        );
+
+        let mut blocks = input.lines().blocks();
+        assert_eq!(blocks.next_back(), Some(vec!["b map:", "4 5 6"]));
+        assert_eq!(blocks.next(), Some(vec!["seeds: 1 2"]));
+        assert_eq!(blocks.next_back(), Some(vec!["a map:", "1 2 3"]));
+        assert_eq!(blocks.next(), None);
+    }
+}
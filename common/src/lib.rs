@@ -0,0 +1,97 @@
+pub mod arith;
+pub mod http;
+pub mod input;
+pub mod iter;
+#[cfg(test)]
+mod mock;
+pub mod normalise;
+pub mod parsing;
+pub mod sections;
+pub mod submit;
+
+pub use aoc_macros::aoc;
+pub use inventory;
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+pub trait Solution {
+    type Output: Display;
+
+    fn solve(&self) -> Self::Output;
+}
+
+pub fn show_solutions<P1, P2>(part1: P1, part2: P2)
+where
+    P1: Solution,
+    P2: Solution,
+{
+    println!("Part 1: {}", part1.solve());
+    println!("Part 2: {}", part2.solve());
+    show_notes();
+}
+
+/// Prints the notes and warnings parsing left on this thread to stderr.
+pub fn show_notes() {
+    for note in parsing::take_notes() {
+        eprintln!("note: {note}");
+    }
+    for skipped in parsing::take_warnings() {
+        eprintln!("warning: skipped {skipped}");
+    }
+}
+
+/// A crate of solutions for one event year, as seen by the runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Year {
+    pub year: u16,
+    pub package: &'static str,
+    pub manifest_dir: &'static str,
+}
+
+/// One part of one day, registered by [`aoc`]. `solve` takes the whole
+/// input and returns the answer as it would be submitted, or why the input
+/// couldn't be parsed.
+#[derive(Debug)]
+pub struct Solver {
+    pub package: &'static str,
+    pub day: u8,
+    pub part: u8,
+    pub solve: fn(&str) -> Result<String, String>,
+}
+
+inventory::collect!(Solver);
+
+impl Year {
+    pub fn manifest_path(&self) -> PathBuf {
+        Path::new(self.manifest_dir).join("Cargo.toml")
+    }
+
+    pub fn input_dir(&self) -> PathBuf {
+        Path::new(self.manifest_dir).join(input::DEFAULT_INPUT_DIR)
+    }
+
+    /// The parts registered for `day` in this year's crate, in order.
+    pub fn solvers(&self, day: u8) -> Vec<&'static Solver> {
+        let mut solvers = inventory::iter::<Solver>
+            .into_iter()
+            .filter(|solver| solver.package == self.package && solver.day == day)
+            .collect::<Vec<_>>();
+        solvers.sort_by_key(|solver| solver.part);
+        solvers
+    }
+
+    /// Every day with at least one part registered.
+    pub fn days(&self) -> Vec<u8> {
+        let mut days = inventory::iter::<Solver>
+            .into_iter()
+            .filter(|solver| solver.package == self.package)
+            .map(|solver| solver.day)
+            .collect::<Vec<_>>();
+        days.sort();
+        days.dedup();
+        days
+    }
+}
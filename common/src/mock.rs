@@ -0,0 +1,62 @@
+//! A tiny HTTP/1.1 stand-in for adventofcode.com, used by tests so nothing
+//! touches the network.
+
+use crate::http::{read_request, Deadline, Request};
+use std::{
+    io::Write,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// Answers and inputs are small, and a request body bigger than this means
+/// a test has gone wrong.
+const MAX_BODY: usize = 1 << 20;
+
+/// How long a test's client gets to send a whole request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Serves every request with `handler`, which returns a status code and
+    /// a body.
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Request) -> (u16, String) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let deadline = &mut Deadline::new(&stream, REQUEST_TIMEOUT);
+                let Ok(request) = read_request(deadline, MAX_BODY) else {
+                    continue;
+                };
+
+                let (status, body) = handler(&request);
+                log.lock().unwrap().push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,208 @@
+use crate::parsing;
+use std::fmt;
+
+/// Cleans up an input before any solution sees it, so parsers only ever
+/// deal with `\n` separated lines and a single trailing newline.
+///
+/// Dedenting is off by default since real inputs aren't indented, but it
+/// lets a test fixture be written as an indented string literal.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Normalise {
+    dedent: bool,
+}
+
+impl Normalise {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also drops leading blank lines and the indentation every line shares.
+    pub fn with_dedent(mut self) -> Self {
+        self.dedent = true;
+        self
+    }
+
+    pub fn apply(&self, input: &str) -> Normalised {
+        let mut changes = Vec::new();
+
+        let input = match input.strip_prefix('\u{feff}') {
+            Some(rest) => {
+                changes.push(Change::RemovedBom);
+                rest
+            }
+            None => input,
+        };
+
+        let crlf = input.matches("\r\n").count();
+        if crlf > 0 {
+            changes.push(Change::ConvertedCrlf(crlf));
+        }
+        let text = input.replace("\r\n", "\n");
+        let mut lines = text.lines().collect::<Vec<_>>();
+
+        if self.dedent {
+            let leading = lines.iter().take_while(|line| is_blank(line)).count();
+            if leading > 0 {
+                lines.drain(..leading);
+                changes.push(Change::RemovedLeadingBlankLines(leading));
+            }
+        }
+
+        let trailing = lines.iter().rev().take_while(|line| is_blank(line)).count();
+        if trailing > 0 {
+            lines.truncate(lines.len() - trailing);
+            changes.push(Change::RemovedTrailingBlankLines(trailing));
+        }
+
+        if self.dedent {
+            let indent = lines
+                .iter()
+                .filter(|line| !is_blank(line))
+                .map(|line| line.len() - line.trim_start().len())
+                .min()
+                .unwrap_or(0);
+            if indent > 0 {
+                for line in &mut lines {
+                    *line = line.get(indent..).unwrap_or("");
+                }
+                changes.push(Change::Dedented(indent));
+            }
+        }
+
+        if trailing == 0 && !lines.is_empty() && !text.ends_with('\n') {
+            changes.push(Change::AddedTrailingNewline);
+        }
+
+        let text = match lines.is_empty() {
+            true => String::new(),
+            false => lines.join("\n") + "\n",
+        };
+
+        Normalised { text, changes }
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// A normalised input along with everything that was done to it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Normalised {
+    pub text: String,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    RemovedBom,
+    ConvertedCrlf(usize),
+    RemovedLeadingBlankLines(usize),
+    RemovedTrailingBlankLines(usize),
+    AddedTrailingNewline,
+    Dedented(usize),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::RemovedBom => write!(f, "removed a byte order mark"),
+            Change::ConvertedCrlf(count) => write!(f, "converted {count} CRLF line endings"),
+            Change::RemovedLeadingBlankLines(count) => {
+                write!(f, "removed {count} blank lines at the start")
+            }
+            Change::RemovedTrailingBlankLines(count) => {
+                write!(f, "removed {count} blank lines at the end")
+            }
+            Change::AddedTrailingNewline => write!(f, "added a trailing newline"),
+            Change::Dedented(columns) => write!(f, "removed {columns} columns of indentation"),
+        }
+    }
+}
+
+/// Normalises a puzzle input the way the runner does, leaving a
+/// [`parsing::note`] for anything that changed.
+pub fn input(raw: &str) -> String {
+    let normalised = Normalise::new().apply(raw);
+    for change in &normalised.changes {
+        parsing::note(format!("input {change}"));
+    }
+    normalised.text
+}
+
+/// Dedents a test fixture written as an indented string literal.
+pub fn fixture(raw: &str) -> String {
+    Normalise::new().with_dedent().apply(raw).text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_clean_input_is_untouched() {
+        let normalised = Normalise::new().apply("a b\n  c\n");
+        assert_eq!(normalised.text, "a b\n  c\n");
+        assert!(normalised.changes.is_empty());
+
+        assert_eq!(Normalise::new().apply("").text, "");
+    }
+
+    #[test]
+    fn test_windows_input() {
+        let normalised = Normalise::new().apply("\u{feff}Time: 7\r\nDistance: 9");
+
+        assert_eq!(normalised.text, "Time: 7\nDistance: 9\n");
+        assert_eq!(
+            normalised.changes,
+            [
+                Change::RemovedBom,
+                Change::ConvertedCrlf(1),
+                Change::AddedTrailingNewline,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_leaves_notes() {
+        parsing::take_notes();
+        assert_eq!(input("1\r\n2"), "1\n2\n");
+        assert_eq!(
+            parsing::take_notes(),
+            [
+                "input converted 1 CRLF line endings",
+                "input added a trailing newline"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let normalised = Normalise::new().apply("1\n2\n\n  \n");
+
+        assert_eq!(normalised.text, "1\n2\n");
+        assert_eq!(normalised.changes, [Change::RemovedTrailingBlankLines(2)]);
+    }
+
+    #[test]
+    fn test_dedent_keeps_relative_indentation() {
+        let normalised = Normalise::new().with_dedent().apply(
+            "
+            467..114..
+              .*......
+
+            ..35..633.
+        ",
+        );
+
+        assert_eq!(normalised.text, "467..114..\n  .*......\n\n..35..633.\n");
+        assert_eq!(
+            normalised.changes,
+            [
+                Change::RemovedLeadingBlankLines(1),
+                Change::RemovedTrailingBlankLines(1),
+                Change::Dedented(12),
+            ]
+        );
+    }
+}
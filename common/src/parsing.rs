@@ -0,0 +1,233 @@
+use std::{
+    cell::{Cell, RefCell},
+    env, fmt,
+    str::FromStr,
+};
+
+/// What a parser does with a line it can't make sense of.
+///
+/// The mode is kept per thread, read from `AOC_PARSE` the first time it's
+/// needed unless [`set_mode`] got there first. A value that isn't a mode
+/// is left as a note for [`take_notes`] and parsing goes on leniently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Skip the line and record a warning for [`take_warnings`].
+    #[default]
+    Lenient,
+    /// Fail the whole parse with a [`BadLine`].
+    Strict,
+}
+
+impl Mode {
+    /// Reads `AOC_PARSE`, which may be `strict` or `lenient`.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("AOC_PARSE") {
+            Ok(mode) => mode.parse(),
+            Err(_) => Ok(Mode::default()),
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lenient" => Ok(Mode::Lenient),
+            "strict" => Ok(Mode::Strict),
+            _ => Err(format!(
+                "unknown parse mode `{s}`, expected strict or lenient"
+            )),
+        }
+    }
+}
+
+thread_local! {
+    static MODE: Cell<Option<Mode>> = const { Cell::new(None) };
+    static WARNINGS: RefCell<Vec<BadLine>> = const { RefCell::new(Vec::new()) };
+    static NOTES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn mode() -> Mode {
+    MODE.with(|mode| match mode.get() {
+        Some(current) => current,
+        None => {
+            let current = or_lenient(Mode::from_env());
+            mode.set(Some(current));
+            current
+        }
+    })
+}
+
+/// The mode asked for, or [`Mode::Lenient`] with a note saying why not.
+fn or_lenient(requested: Result<Mode, String>) -> Mode {
+    requested.unwrap_or_else(|err| {
+        note(format!("{err}, parsing leniently"));
+        Mode::default()
+    })
+}
+
+pub fn set_mode(mode: Mode) {
+    MODE.with(|current| current.set(Some(mode)));
+}
+
+/// Everything skipped on this thread since the last call, in the order it
+/// was found. A line skipped by both parts of a day is only listed once.
+pub fn take_warnings() -> Vec<BadLine> {
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+/// Leaves a message about how the input was read for whoever runs the
+/// solution to show, since solutions themselves don't print.
+pub fn note(message: impl Into<String>) {
+    NOTES.with(|notes| notes.borrow_mut().push(message.into()));
+}
+
+/// Every note left on this thread since the last call.
+pub fn take_notes() -> Vec<String> {
+    NOTES.with(|notes| notes.take())
+}
+
+/// A line of input that didn't parse, numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadLine {
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl fmt::Display for BadLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: `{}`", self.line, self.reason, self.text)
+    }
+}
+
+impl std::error::Error for BadLine {}
+
+/// Parses every non-blank line of `input`, trimmed, skipping or failing on
+/// the ones `parse` rejects depending on the current [`Mode`].
+pub fn lines<'a, T, E, F>(input: &'a str, parse: F) -> Result<Vec<T>, BadLine>
+where
+    F: FnMut(&'a str) -> Result<T, E>,
+    E: fmt::Display,
+{
+    let numbered = numbered(input, parse)?;
+    Ok(numbered.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Like [`lines`], but keeps the line number each item came from for
+/// checks that span more than one line.
+pub fn numbered<'a, T, E, F>(input: &'a str, mut parse: F) -> Result<Vec<(usize, T)>, BadLine>
+where
+    F: FnMut(&'a str) -> Result<T, E>,
+    E: fmt::Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .filter_map(|(line, text)| check(input, line, parse(text).map(|item| (line, item))))
+        .collect()
+}
+
+/// Applies the current [`Mode`] to what was made of line `line` of `input`,
+/// whether that's the line parsed on its own or a problem only found
+/// afterwards, such as one line contradicting another.
+///
+/// Gives back `None` when the item should be skipped, so this slots into a
+/// `filter_map` ahead of collecting into a `Result`.
+pub fn check<T, E>(input: &str, line: usize, result: Result<T, E>) -> Option<Result<T, BadLine>>
+where
+    E: fmt::Display,
+{
+    let err = match result {
+        Ok(item) => return Some(Ok(item)),
+        Err(err) => err,
+    };
+    let bad = BadLine {
+        line,
+        text: input
+            .lines()
+            .nth(line - 1)
+            .map_or("", str::trim)
+            .to_string(),
+        reason: err.to_string(),
+    };
+
+    match mode() {
+        Mode::Strict => Some(Err(bad)),
+        Mode::Lenient => {
+            warn(bad);
+            None
+        }
+    }
+}
+
+fn warn(bad: BadLine) {
+    WARNINGS.with(|warnings| {
+        let mut warnings = warnings.borrow_mut();
+        if !warnings.contains(&bad) {
+            warnings.push(bad);
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_all(input: &str) -> Result<Vec<u32>, BadLine> {
+        lines(input, |line| line.parse::<u32>())
+    }
+
+    #[test]
+    fn test_lenient_skips_and_warns() {
+        set_mode(Mode::Lenient);
+        take_warnings();
+
+        let parsed = parse_all("1\n\nx\n3\n");
+        assert_eq!(parsed, Ok(vec![1, 3]));
+
+        // a second pass over the same input doesn't repeat itself
+        parse_all("1\n\nx\n3\n").unwrap();
+        let warnings = take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 3: invalid digit found in string: `x`"
+        );
+        assert!(take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_strict_fails_on_first_bad_line() {
+        set_mode(Mode::Strict);
+        take_warnings();
+
+        let err = parse_all("1\n  x  \ny").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "x");
+        assert!(take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_bad_mode_is_noted() {
+        take_notes();
+        assert_eq!(or_lenient("strict".parse()), Mode::Strict);
+        assert!(take_notes().is_empty());
+
+        assert_eq!(or_lenient("loose".parse()), Mode::Lenient);
+        assert_eq!(
+            take_notes(),
+            ["unknown parse mode `loose`, expected strict or lenient, parsing leniently"]
+        );
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!("strict".parse(), Ok(Mode::Strict));
+        assert_eq!("lenient".parse(), Ok(Mode::Lenient));
+        assert!("loose".parse::<Mode>().is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use std::{fmt, str::FromStr};
+
+/// One blank-line separated block of an input.
+///
+/// A block whose first line contains a `:` is named after whatever comes
+/// before it, so `seed-to-soil map:` is named `seed-to-soil map` and
+/// `seeds: 79 14` is named `seeds` with `79 14` as its only body line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub index: usize,
+    pub line: usize,
+    pub name: Option<&'a str>,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    fn new(index: usize, line: usize, mut lines: Vec<&'a str>) -> Self {
+        let name = match lines[0].split_once(':') {
+            Some((name, rest)) => {
+                let rest = rest.trim();
+                if rest.is_empty() {
+                    lines.remove(0);
+                } else {
+                    lines[0] = rest;
+                }
+                Some(name.trim())
+            }
+            None => None,
+        };
+
+        Self {
+            index,
+            line,
+            name,
+            lines,
+        }
+    }
+
+    pub fn body(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn parse<T>(&self) -> Result<T, SectionError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.body()
+            .parse()
+            .map_err(|err: T::Err| self.error(err.to_string()))
+    }
+
+    pub fn error(&self, reason: impl Into<String>) -> SectionError {
+        self.error_at(self.line, reason)
+    }
+
+    /// Like [`Section::error`], for a problem with one line of the section
+    /// rather than the section as a whole.
+    pub fn error_at(&self, line: usize, reason: impl Into<String>) -> SectionError {
+        SectionError {
+            index: self.index,
+            line,
+            name: self.name.map(str::to_string),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// An input split into [`Section`]s, with line numbers counted from the
+/// start of the original text.
+#[derive(Debug)]
+pub struct Sections<'a> {
+    pub sections: Vec<Section<'a>>,
+}
+
+impl<'a> Sections<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let mut sections = Vec::new();
+        let mut block = Vec::new();
+        let mut start = 0;
+
+        for (idx, line) in input.lines().map(str::trim).enumerate() {
+            match line.is_empty() {
+                true if block.is_empty() => continue,
+                true => sections.push(Section::new(sections.len(), start, block.split_off(0))),
+                false => {
+                    if block.is_empty() {
+                        start = idx + 1;
+                    }
+                    block.push(line);
+                }
+            }
+        }
+
+        if !block.is_empty() {
+            sections.push(Section::new(sections.len(), start, block));
+        }
+
+        Self { sections }
+    }
+
+    /// Checks that the sections are named exactly `names`, in that order.
+    pub fn expect_order(&self, names: &[&str]) -> Result<(), SectionError> {
+        self.expect_prefix(names)?;
+
+        match self.sections.get(names.len()) {
+            Some(extra) => Err(extra.error("unexpected section")),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the first sections are named `names`, in that order,
+    /// allowing anything after them.
+    pub fn expect_prefix(&self, names: &[&str]) -> Result<(), SectionError> {
+        for (idx, expected) in names.iter().enumerate() {
+            match self.sections.get(idx) {
+                Some(section) if section.name == Some(expected) => continue,
+                Some(section) => {
+                    return Err(section.error(format!("expected section `{expected}`")));
+                }
+                None => {
+                    return Err(SectionError {
+                        index: idx,
+                        line: self.sections.last().map_or(0, |s| s.line),
+                        name: None,
+                        reason: format!("missing section `{expected}`"),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Section<'a>> {
+        self.sections.iter().find(|s| s.name == Some(name))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Section<'a>> {
+        self.sections.iter()
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s Sections<'a> {
+    type Item = &'s Section<'a>;
+    type IntoIter = std::slice::Iter<'s, Section<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Which section went wrong and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionError {
+    pub index: usize,
+    pub line: usize,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "section {}", self.index + 1)?;
+        if let Some(name) = &self.name {
+            write!(f, " `{name}`")?;
+        }
+        write!(f, " (line {}): {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for SectionError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "
+        seeds: 79 14 55 13
+
+        seed-to-soil map:
+        50 98 2
+        52 50 48
+
+        soil-to-fertilizer map:
+        0 15 37
+    ";
+
+    #[test]
+    fn test_parse_sections() {
+        let sections = Sections::parse(INPUT);
+
+        assert_eq!(
+            sections.sections,
+            vec![
+                Section {
+                    index: 0,
+                    line: 2,
+                    name: Some("seeds"),
+                    lines: vec!["79 14 55 13"],
+                },
+                Section {
+                    index: 1,
+                    line: 4,
+                    name: Some("seed-to-soil map"),
+                    lines: vec!["50 98 2", "52 50 48"],
+                },
+                Section {
+                    index: 2,
+                    line: 8,
+                    name: Some("soil-to-fertilizer map"),
+                    lines: vec!["0 15 37"],
+                },
+            ]
+        );
+        assert_eq!(
+            sections.get("seed-to-soil map").unwrap().body(),
+            "50 98 2\n52 50 48"
+        );
+    }
+
+    #[test]
+    fn test_unnamed_section() {
+        let sections = Sections::parse("1 2\n3 4\n\n5 6");
+
+        assert_eq!(sections.sections[0].name, None);
+        assert_eq!(sections.sections[0].lines, vec!["1 2", "3 4"]);
+        assert_eq!(sections.sections[1].line, 4);
+    }
+
+    #[test]
+    fn test_expect_order() {
+        let sections = Sections::parse(INPUT);
+
+        assert_eq!(
+            sections.expect_order(&["seeds", "seed-to-soil map", "soil-to-fertilizer map"]),
+            Ok(())
+        );
+
+        let err = sections
+            .expect_order(&["seeds", "soil-to-fertilizer map", "seed-to-soil map"])
+            .unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.line, 4);
+        assert_eq!(
+            err.to_string(),
+            "section 2 `seed-to-soil map` (line 4): expected section `soil-to-fertilizer map`"
+        );
+
+        let err = sections
+            .expect_order(&["seeds", "seed-to-soil map"])
+            .unwrap_err();
+        assert_eq!(err.reason, "unexpected section");
+        assert_eq!(
+            sections.expect_prefix(&["seeds", "seed-to-soil map"]),
+            Ok(())
+        );
+
+        let err = sections
+            .expect_order(&["seeds", "seed-to-soil map", "soil-to-fertilizer map", "x"])
+            .unwrap_err();
+        assert_eq!(err.reason, "missing section `x`");
+    }
+
+    #[test]
+    fn test_parse_reports_section() {
+        let sections = Sections::parse(INPUT);
+
+        let err = sections.sections[1].parse::<u64>().unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.name.as_deref(), Some("seed-to-soil map"));
+        assert_eq!(err.reason, "invalid digit found in string");
+
+        let err = sections.sections[1].error_at(5, "bad row");
+        assert_eq!(
+            err.to_string(),
+            "section 2 `seed-to-soil map` (line 5): bad row"
+        );
+    }
+}
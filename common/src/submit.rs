@@ -0,0 +1,487 @@
+use crate::input::{FetchError, InputCache};
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The server's judgement of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Rate limited, for as long as the page said if it could be read.
+    Wait(Option<Duration>),
+    AlreadySolved,
+}
+
+/// How long to hold off after a rate limit whose wait couldn't be read: the
+/// shortest the site hands out after a wrong answer.
+const UNKNOWN_WAIT: Duration = Duration::from_secs(60);
+
+impl Verdict {
+    /// Reads the verdict out of the HTML page returned after posting an
+    /// answer.
+    pub fn parse(body: &str) -> Option<Verdict> {
+        if body.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if body.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if body.contains("That's not the right answer") {
+            Some(Verdict::Incorrect)
+        } else if body.contains("You gave an answer too recently") {
+            Some(Verdict::Wait(parse_wait(body)))
+        } else if body.contains("Did you already complete it") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the verdict says something about the answer itself, as
+    /// opposed to when or whether it could be checked.
+    pub fn is_judgement(&self) -> bool {
+        !matches!(self, Verdict::Wait(_) | Verdict::AlreadySolved)
+    }
+}
+
+fn parse_wait(body: &str) -> Option<Duration> {
+    let (_, rest) = body.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .try_fold(0, |secs, part| match part.strip_suffix('m') {
+            Some(mins) => Some(secs + mins.parse::<u64>().ok()? * 60),
+            None => Some(secs + part.strip_suffix('s')?.parse::<u64>().ok()?),
+        })
+        .map(Duration::from_secs)
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(Some(wait)) => write!(f, "wait:{}", wait.as_secs()),
+            Verdict::Wait(None) => write!(f, "wait"),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            "wait" => Ok(Verdict::Wait(None)),
+            s => s
+                .strip_prefix("wait:")
+                .and_then(|secs| secs.parse().ok())
+                .map(|secs| Verdict::Wait(Some(Duration::from_secs(secs))))
+                .ok_or("unknown verdict"),
+        }
+    }
+}
+
+/// One line of the submission history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp, self.year, self.day, self.part, self.answer, self.verdict
+        )
+    }
+}
+
+impl FromStr for Record {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields = s.split('\t').collect::<Vec<_>>();
+        let [timestamp, year, day, part, answer, verdict] = fields[..] else {
+            return Err("expected six tab separated fields");
+        };
+
+        Ok(Record {
+            timestamp: timestamp.parse().map_err(|_| "invalid timestamp")?,
+            year: year.parse().map_err(|_| "invalid year")?,
+            day: day.parse().map_err(|_| "invalid day")?,
+            part: part.parse().map_err(|_| "invalid part")?,
+            answer: answer.to_string(),
+            verdict: verdict.parse()?,
+        })
+    }
+}
+
+/// Every answer ever submitted, appended to a tab separated file.
+pub struct History {
+    path: PathBuf,
+    pub records: Vec<Record>,
+}
+
+impl History {
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let records = match fs::read_to_string(&path) {
+            Ok(contents) => contents
+                .lines()
+                .filter_map(|line| line.parse().ok())
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Self { path, records })
+    }
+
+    /// The history kept next to a cache's inputs.
+    pub fn for_cache(cache: &InputCache) -> io::Result<Self> {
+        Self::load(cache.dir().join("submissions.tsv"))
+    }
+
+    pub fn record(&mut self, record: Record) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{record}")?;
+
+        self.records.push(record);
+        Ok(())
+    }
+
+    pub fn for_part(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Record> {
+        self.records
+            .iter()
+            .filter(move |r| r.year == year && r.day == day && r.part == part)
+    }
+
+    /// What the server made of `answer`, if it has seen it or has already
+    /// accepted a different one.
+    pub fn verdict(&self, year: u16, day: u8, part: u8, answer: &str) -> Option<Verdict> {
+        let records = self.for_part(year, day, part).collect::<Vec<_>>();
+
+        let seen = records
+            .iter()
+            .rev()
+            .find(|r| r.answer == answer && r.verdict.is_judgement());
+        if let Some(record) = seen {
+            return Some(record.verdict.clone());
+        }
+
+        records
+            .iter()
+            .any(|r| r.verdict == Verdict::Correct)
+            .then_some(Verdict::Incorrect)
+    }
+
+    /// Decides whether `answer` is worth sending, based on what the server
+    /// has already told us about this part and, since rate limits are per
+    /// account, whether the last submission of any part is still waiting.
+    fn check(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Option<Outcome> {
+        let records = self.for_part(year, day, part).collect::<Vec<_>>();
+
+        if let Some(correct) = records.iter().find(|r| r.verdict == Verdict::Correct) {
+            return Some(match correct.answer == answer {
+                true => Outcome::Duplicate(Verdict::Correct),
+                false => Outcome::AlreadySolved {
+                    answer: correct.answer.clone(),
+                },
+            });
+        }
+
+        if let Some(Record {
+            timestamp,
+            verdict: Verdict::Wait(wait),
+            ..
+        }) = self.records.last()
+        {
+            let ready_at = timestamp + wait.unwrap_or(UNKNOWN_WAIT).as_secs();
+            if now < ready_at {
+                return Some(Outcome::Cooldown(Duration::from_secs(ready_at - now)));
+            }
+        }
+
+        if let Some(previous) = records
+            .iter()
+            .find(|r| r.answer == answer && r.verdict.is_judgement())
+        {
+            return Some(Outcome::Duplicate(previous.verdict.clone()));
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        records.iter().find_map(|r| {
+            let bound = r.answer.parse::<i128>().ok()?;
+            match r.verdict {
+                Verdict::TooHigh if value >= bound => Some(Outcome::OutOfBounds(Verdict::TooHigh)),
+                Verdict::TooLow if value <= bound => Some(Outcome::OutOfBounds(Verdict::TooLow)),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// What happened to an answer handed to [`Submitter::submit`]. Everything
+/// other than `Submitted` was settled locally without contacting the server.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Submitted(Verdict),
+    Duplicate(Verdict),
+    OutOfBounds(Verdict),
+    AlreadySolved { answer: String },
+    Cooldown(Duration),
+}
+
+/// Posts answers using the same server and session as an [`InputCache`],
+/// keeping the history next to the cached inputs.
+pub struct Submitter {
+    year: u16,
+    base_url: String,
+    session: Option<String>,
+    pub history: History,
+}
+
+impl Submitter {
+    pub fn new(cache: &InputCache) -> io::Result<Self> {
+        Ok(Self {
+            year: cache.year(),
+            base_url: cache.base_url().to_string(),
+            session: cache.session().map(str::to_string),
+            history: History::for_cache(cache)?,
+        })
+    }
+
+    pub fn submit(&mut self, day: u8, part: u8, answer: &str) -> Result<Outcome, FetchError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0);
+
+        if let Some(outcome) = self.history.check(self.year, day, part, answer, now) {
+            return Ok(outcome);
+        }
+
+        let session = self.session.as_ref().ok_or(FetchError::MissingSession)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+        let body = ureq::post(&url)
+            .set("Cookie", &format!("session={session}"))
+            .set("User-Agent", crate::input::USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let verdict =
+            Verdict::parse(&body).ok_or_else(|| FetchError::UnexpectedResponse(body.clone()))?;
+
+        self.history.record(Record {
+            timestamp: now,
+            year: self.year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        })?;
+
+        Ok(Outcome::Submitted(verdict))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::MockServer;
+    use std::env;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-submit-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn test_parse_verdicts() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", Verdict::Correct),
+            ("That's not the right answer; your answer is too high.", Verdict::TooHigh),
+            ("That's not the right answer; your answer is too low.", Verdict::TooLow),
+            ("That's not the right answer. If you're stuck...", Verdict::Incorrect),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 22s left to wait.",
+                Verdict::Wait(Some(Duration::from_secs(82))),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::AlreadySolved,
+            ),
+        ];
+
+        for (message, expected) in cases {
+            assert_eq!(Verdict::parse(&page(message)), Some(expected));
+        }
+        assert_eq!(Verdict::parse("<html></html>"), None);
+
+        // A wait that can't be read is unknown, not over.
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently.  You have a while left to wait."
+            )),
+            Some(Verdict::Wait(None))
+        );
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let record = Record {
+            timestamp: 1701763200,
+            year: 2023,
+            day: 5,
+            part: 2,
+            answer: "46".to_string(),
+            verdict: Verdict::Wait(Some(Duration::from_secs(60))),
+        };
+        assert_eq!(record.to_string().parse(), Ok(record.clone()));
+
+        let record = Record {
+            verdict: Verdict::Wait(None),
+            ..record
+        };
+        assert_eq!(record.to_string().parse(), Ok(record));
+    }
+
+    #[test]
+    fn test_submit_records_and_skips_known_answers() {
+        let server = MockServer::start(|req| match req.body.as_str() {
+            "level=1&answer=100" => (
+                200,
+                page("That's not the right answer; your answer is too high."),
+            ),
+            "level=1&answer=42" => (200, page("That's the right answer!")),
+            _ => (200, page("That's not the right answer.")),
+        });
+        let dir = temp_dir("skip");
+        let cache = InputCache::new(2023, &dir)
+            .with_base_url(&server.url)
+            .with_session("abc123");
+        let mut submitter = Submitter::new(&cache).unwrap();
+
+        assert_eq!(
+            submitter.submit(1, 1, "100").unwrap(),
+            Outcome::Submitted(Verdict::TooHigh)
+        );
+        assert_eq!(
+            submitter.submit(1, 1, "100").unwrap(),
+            Outcome::Duplicate(Verdict::TooHigh)
+        );
+        assert_eq!(
+            submitter.submit(1, 1, "150").unwrap(),
+            Outcome::OutOfBounds(Verdict::TooHigh)
+        );
+        assert_eq!(
+            submitter.submit(1, 1, "42").unwrap(),
+            Outcome::Submitted(Verdict::Correct)
+        );
+        assert_eq!(
+            submitter.submit(1, 1, "43").unwrap(),
+            Outcome::AlreadySolved {
+                answer: "42".to_string()
+            }
+        );
+        assert_eq!(server.requests().len(), 2);
+
+        let history = History::load(dir.join("submissions.tsv")).unwrap();
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(history.records[1].verdict, Verdict::Correct);
+        assert_eq!(history.verdict(2023, 1, 1, "42"), Some(Verdict::Correct));
+        assert_eq!(history.verdict(2023, 1, 1, "100"), Some(Verdict::TooHigh));
+        assert_eq!(history.verdict(2023, 1, 1, "7"), Some(Verdict::Incorrect));
+        assert_eq!(history.verdict(2023, 1, 2, "42"), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit_respects_cooldown() {
+        let server = MockServer::start(|_| {
+            (
+                200,
+                page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 5m 0s left to wait."),
+            )
+        });
+        let dir = temp_dir("cooldown");
+        let cache = InputCache::new(2023, &dir)
+            .with_base_url(&server.url)
+            .with_session("abc123");
+        let mut submitter = Submitter::new(&cache).unwrap();
+
+        assert_eq!(
+            submitter.submit(2, 1, "8").unwrap(),
+            Outcome::Submitted(Verdict::Wait(Some(Duration::from_secs(300))))
+        );
+        assert!(matches!(
+            submitter.submit(2, 1, "9").unwrap(),
+            Outcome::Cooldown(wait) if wait.as_secs() > 290
+        ));
+        // The limit is on the account, not the part.
+        assert!(matches!(
+            submitter.submit(3, 2, "10").unwrap(),
+            Outcome::Cooldown(wait) if wait.as_secs() > 290
+        ));
+        assert_eq!(server.requests().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_wait() {
+        let history = History {
+            path: PathBuf::new(),
+            records: vec![Record {
+                timestamp: 1000,
+                year: 2023,
+                day: 4,
+                part: 1,
+                answer: "7".to_string(),
+                verdict: Verdict::Wait(None),
+            }],
+        };
+
+        assert_eq!(
+            history.check(2023, 5, 1, "8", 1010),
+            Some(Outcome::Cooldown(Duration::from_secs(50)))
+        );
+        assert_eq!(history.check(2023, 5, 1, "8", 1060), None);
+    }
+}
@@ -0,0 +1,138 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, FnArg, ItemFn, LitInt, Path, ReturnType, Type};
+
+/// Turns a function into one part of a day's solution.
+///
+/// ```ignore
+/// #[aoc(day = 5, part = 2)]
+/// fn part2((seeds, almanac): &(Vec<u64>, Almanac)) -> u64 { .. }
+/// ```
+///
+/// The function takes a reference to whatever the module's `parse` returns
+/// inside its `Result`, or `&str` to work on the raw input. Alongside it this
+/// generates `PartOne` or `PartTwo` implementing `Solution`, and registers
+/// the part with the runner.
+///
+/// A part that needs its own reading of the input can name another parser
+/// with `parse = <fn>`; it is held to the same signature as `parse`.
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut day = None;
+    let mut part = None;
+    let mut parse = None;
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("parse") {
+            parse = Some(meta.value()?.parse::<Path>()?);
+            return Ok(());
+        }
+        let value = meta.value()?.parse::<LitInt>()?.base10_parse::<u8>()?;
+        if meta.path.is_ident("day") {
+            day = Some(value);
+        } else if meta.path.is_ident("part") {
+            part = Some(value);
+        } else {
+            return Err(meta.error("expected `day`, `part` or `parse`"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(args with parser);
+
+    let func = parse_macro_input!(item as ItemFn);
+
+    match expand(day, part, parse, func) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(
+    day: Option<u8>,
+    part: Option<u8>,
+    parse: Option<Path>,
+    func: ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let day = day.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `day = <n>`"))?;
+    let part = part.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `part = <n>`"))?;
+
+    let solution = match part {
+        1 => format_ident!("PartOne"),
+        2 => format_ident!("PartTwo"),
+        _ => return Err(syn::Error::new(Span::call_site(), "`part` must be 1 or 2")),
+    };
+
+    let inputs = func.sig.inputs.iter().collect::<Vec<_>>();
+    let [FnArg::Typed(input)] = inputs[..] else {
+        return Err(syn::Error::new(
+            func.sig.inputs.span(),
+            "expected a single argument taking the parsed input",
+        ));
+    };
+    let Type::Reference(input_ty) = &*input.ty else {
+        return Err(syn::Error::new(
+            input.ty.span(),
+            "expected the input by reference",
+        ));
+    };
+    let raw = matches!(&*input_ty.elem, Type::Path(path) if path.path.is_ident("str"));
+    if raw && parse.is_some() {
+        return Err(syn::Error::new(
+            input.ty.span(),
+            "`parse` has nothing to do for a part taking `&str`",
+        ));
+    }
+    let parse = parse.unwrap_or_else(|| format_ident!("parse").into());
+
+    let ReturnType::Type(_, output) = &func.sig.output else {
+        return Err(syn::Error::new(
+            func.sig.span(),
+            "expected the function to return its answer",
+        ));
+    };
+
+    let name = &func.sig.ident;
+    let (solve, register) = match raw {
+        true => (
+            quote! { #name(self.0) },
+            quote! { Ok(#name(input).to_string()) },
+        ),
+        false => (
+            quote! { #name(&#parse(self.0).expect("failed to parse input")) },
+            quote! {
+                let parsed = #parse(input).map_err(|err| err.to_string())?;
+                Ok(#name(&parsed).to_string())
+            },
+        ),
+    };
+
+    Ok(quote! {
+        #func
+
+        pub struct #solution<'a>(&'a str);
+
+        impl<'a> #solution<'a> {
+            pub fn new(input: &'a str) -> Self {
+                Self(input)
+            }
+        }
+
+        impl ::aoc_common::Solution for #solution<'_> {
+            type Output = #output;
+
+            fn solve(&self) -> Self::Output {
+                #solve
+            }
+        }
+
+        ::aoc_common::inventory::submit! {
+            ::aoc_common::Solver {
+                package: env!("CARGO_PKG_NAME"),
+                day: #day,
+                part: #part,
+                solve: |input| { #register },
+            }
+        }
+    })
+}
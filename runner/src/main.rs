@@ -0,0 +1,234 @@
+use aoc_2023::day4;
+use aoc_common::{
+    input::InputCache,
+    parsing::{self, Mode},
+    submit::{Outcome, Submitter, Verdict},
+    Year,
+};
+use std::{env, process::ExitCode};
+
+mod run;
+mod serve;
+mod tui;
+mod watch;
+
+/// Every year the runner knows about, oldest first.
+const YEARS: &[Year] = &[aoc_2023::YEAR];
+
+const USAGE: &str = "usage: aoc [--year <year>] [<options>] run <day>
+       aoc [--year <year>] fetch <day>
+       aoc [--year <year>] [<options>] submit <day> <part>
+       aoc [--year <year>] [<options>] watch <day>
+       aoc [--year <year>] [<options>] tui
+       aoc [<options>] serve [<address>]
+
+options: --strict | --lenient    how to treat bad lines of input
+         --past-end <clamp | error>
+                                 what 2023 day 4 does with a card that wins
+                                 copies of more cards than follow it";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+
+    // The flags override `AOC_PARSE` for every parser the solutions run.
+    let mut mode = Mode::from_env().unwrap_or_else(|err| {
+        eprintln!("warning: {err}, parsing leniently");
+        Mode::default()
+    });
+    let modes = [("--strict", Mode::Strict), ("--lenient", Mode::Lenient)];
+    for (flag, flag_mode) in modes {
+        if let Some(idx) = args.iter().position(|arg| arg == flag) {
+            args.remove(idx);
+            mode = flag_mode;
+        }
+    }
+    parsing::set_mode(mode);
+
+    // Overrides `AOC_PAST_END` the same way.
+    if let Some(idx) = args.iter().position(|arg| arg == "--past-end") {
+        if idx + 1 == args.len() {
+            return usage();
+        }
+        match args.remove(idx + 1).parse() {
+            Ok(past_end) => day4::set_past_end(past_end),
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+        args.remove(idx);
+    }
+
+    let year = match args.iter().position(|arg| arg == "--year") {
+        Some(idx) if idx + 1 < args.len() => {
+            let year = args.remove(idx + 1);
+            args.remove(idx);
+            match YEARS.iter().find(|y| y.year.to_string() == year) {
+                Some(year) => year,
+                None => {
+                    eprintln!("no solutions registered for {year}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Some(_) => return usage(),
+        None => YEARS.last().unwrap(),
+    };
+
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["run", day] => match day.parse() {
+            Ok(day) => run(year, day),
+            Err(_) => usage(),
+        },
+        ["fetch", day] => match day.parse() {
+            Ok(day) => fetch(year, day),
+            Err(_) => usage(),
+        },
+        ["submit", day, part] => match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part @ 1..=2)) => submit(year, day, part),
+            _ => usage(),
+        },
+        ["watch", day] => match day.parse() {
+            Ok(day) => watch::watch(year, day),
+            Err(_) => usage(),
+        },
+        ["tui"] => tui::run(year),
+        ["serve"] => serve::serve(YEARS, serve::DEFAULT_ADDR),
+        ["serve", addr] => serve::serve(YEARS, addr),
+        _ => usage(),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn run(year: &Year, day: u8) -> ExitCode {
+    match solve(year, day) {
+        Ok(answers) => {
+            for (part, answer) in &answers {
+                println!("Part {part}: {answer}");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{} day {day}: {err}", year.year);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(year: &Year, day: u8) -> ExitCode {
+    let cache = InputCache::from_env(year);
+    let cached = cache.is_cached(day);
+
+    match cache.get(day) {
+        Ok(input) => {
+            let status = if cached { "cached" } else { "fetched" };
+            println!(
+                "day {day}: {status} {} bytes at {}",
+                input.len(),
+                cache.path(day).display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(year: &Year, day: u8, part: u8) -> ExitCode {
+    let answer = match solve(year, day) {
+        Ok(answers) => answers
+            .into_iter()
+            .find_map(|(p, answer)| (p == part).then_some(answer)),
+        Err(err) => {
+            eprintln!("day {day}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(answer) = answer else {
+        eprintln!("day {day}: no answer for part {part}");
+        return ExitCode::FAILURE;
+    };
+    println!("day {day} part {part}: submitting {answer}");
+
+    let cache = InputCache::from_env(year);
+    let outcome = Submitter::new(&cache)
+        .map_err(Into::into)
+        .and_then(|mut submitter| submitter.submit(day, part, &answer));
+
+    match outcome {
+        Ok(Outcome::Submitted(verdict)) => {
+            println!("{}", describe(&verdict));
+            match verdict {
+                Verdict::Correct => ExitCode::SUCCESS,
+                _ => ExitCode::FAILURE,
+            }
+        }
+        Ok(Outcome::Duplicate(verdict)) => {
+            println!("already submitted, {}", describe(&verdict));
+            match verdict {
+                Verdict::Correct => ExitCode::SUCCESS,
+                _ => ExitCode::FAILURE,
+            }
+        }
+        Ok(Outcome::OutOfBounds(verdict)) => {
+            println!(
+                "not sent, an earlier answer showed this is {}",
+                describe(&verdict)
+            );
+            ExitCode::FAILURE
+        }
+        Ok(Outcome::AlreadySolved { answer }) => {
+            println!("not sent, this part was already solved with {answer}");
+            ExitCode::FAILURE
+        }
+        Ok(Outcome::Cooldown(wait)) => {
+            println!("not sent, wait {}s before submitting again", wait.as_secs());
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn describe(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Correct => "correct".to_string(),
+        Verdict::TooHigh => "too high".to_string(),
+        Verdict::TooLow => "too low".to_string(),
+        Verdict::Incorrect => "incorrect".to_string(),
+        Verdict::Wait(Some(wait)) => format!("rate limited, wait {}s", wait.as_secs()),
+        Verdict::Wait(None) => "rate limited for a while".to_string(),
+        Verdict::AlreadySolved => "already solved".to_string(),
+    }
+}
+
+/// Solves every registered part of the day with the input from the cache,
+/// fetching it first if it isn't there yet.
+fn solve(year: &Year, day: u8) -> Result<Vec<(u8, String)>, String> {
+    // Checked up front so an unsolved day isn't downloaded for nothing.
+    if year.solvers(day).is_empty() {
+        return Err("no solution registered".to_string());
+    }
+
+    let input = InputCache::from_env(year)
+        .get(day)
+        .map_err(|err| err.to_string())?;
+    let run = run::run(year, day, &input)?;
+
+    for change in &run.changes {
+        eprintln!("note: input {change}");
+    }
+    for skipped in &run.warnings {
+        eprintln!("warning: skipped {skipped}");
+    }
+
+    run.answers()
+}
@@ -0,0 +1,182 @@
+use aoc_common::{
+    normalise::{Change, Normalise},
+    parsing::{self, BadLine},
+    Solver, Year,
+};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+/// One part's answer, or why there isn't one, and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+/// Every registered part of a day run on one input, along with what
+/// normalising and parsing that input turned up.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayRun {
+    pub parts: Vec<PartRun>,
+    pub changes: Vec<Change>,
+    pub warnings: Vec<BadLine>,
+}
+
+impl DayRun {
+    /// The answers in part order, or the first part's error.
+    pub fn answers(&self) -> Result<Vec<(u8, String)>, String> {
+        self.parts
+            .iter()
+            .map(|run| Ok((run.part, run.answer.clone()?)))
+            .collect()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.parts.iter().map(|run| run.elapsed).sum()
+    }
+}
+
+/// Runs every registered part of `day` on `input`, timing each one.
+///
+/// A part that panics is reported as failing rather than taking the
+/// caller down with it.
+pub fn run(year: &Year, day: u8, input: &str) -> Result<DayRun, String> {
+    let solvers = year.solvers(day);
+    if solvers.is_empty() {
+        return Err("no solution registered".to_string());
+    }
+
+    Ok(run_solvers(solvers, input))
+}
+
+/// Like [`run`], for just one part of the day.
+pub fn run_part(year: &Year, day: u8, part: u8, input: &str) -> Result<DayRun, String> {
+    let solvers = year
+        .solvers(day)
+        .into_iter()
+        .filter(|solver| solver.part == part)
+        .collect::<Vec<_>>();
+    if solvers.is_empty() {
+        return Err(format!("no solution registered for part {part}"));
+    }
+
+    Ok(run_solvers(solvers, input))
+}
+
+fn run_solvers(solvers: Vec<&Solver>, input: &str) -> DayRun {
+    let normalised = Normalise::new().apply(input);
+    parsing::take_warnings();
+
+    let parts = solvers
+        .into_iter()
+        .map(|solver| {
+            let start = Instant::now();
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| (solver.solve)(&normalised.text)))
+                .unwrap_or_else(|panic| Err(panic_message(panic)));
+
+            PartRun {
+                part: solver.part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    DayRun {
+        parts,
+        changes: normalised.changes,
+        warnings: parsing::take_warnings(),
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let message = match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown reason".to_string(),
+        },
+    };
+    format!("panicked: {message}")
+}
+
+/// `1.23ms` and the like, with three significant figures or so.
+pub fn format_duration(elapsed: Duration) -> String {
+    let micros = elapsed.as_secs_f64() * 1e6;
+    match micros {
+        m if m < 1e3 => format!("{m:.0}µs"),
+        m if m < 1e6 => format!("{:.2}ms", m / 1e3),
+        m => format!("{:.2}s", m / 1e6),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run_collects_answers_and_warnings() {
+        let run = run(&aoc_2023::YEAR, 1, "1abc2\r\nnope\r\ntreb7uchet").unwrap();
+
+        assert_eq!(
+            run.answers(),
+            Ok(vec![(1, "89".to_string()), (2, "89".to_string())])
+        );
+        assert_eq!(
+            run.changes,
+            [Change::ConvertedCrlf(2), Change::AddedTrailingNewline]
+        );
+        assert_eq!(run.warnings.len(), 1);
+        assert_eq!(run.warnings[0].line, 2);
+    }
+
+    #[test]
+    fn test_run_reports_failures() {
+        assert_eq!(
+            run(&aoc_2023::YEAR, 25, "").unwrap_err(),
+            "no solution registered"
+        );
+
+        let run = run(&aoc_2023::YEAR, 6, "Time: x\nDistance: 9").unwrap();
+        assert_eq!(
+            run.parts[0].answer,
+            Err("failed to parse times".to_string())
+        );
+        assert_eq!(run.parts[1].answer, Err("failed to parse time".to_string()));
+        assert!(run.answers().is_err());
+    }
+
+    #[test]
+    fn test_past_end_is_a_failure_not_a_panic() {
+        aoc_2023::day4::set_past_end(aoc_2023::day4::PastEnd::Error);
+        parsing::set_mode(parsing::Mode::Strict);
+
+        let run = run_part(&aoc_2023::YEAR, 4, 2, "Card 1: 1 | 1\nCard 2: 5 | 5").unwrap();
+        assert_eq!(
+            run.parts[0].answer,
+            Err("line 2: card 2 wins copies of the next 1 cards but only 0 follow it: `Card 2: 5 | 5`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_part() {
+        let run = run_part(&aoc_2023::YEAR, 6, 2, "Time: 7 15 30\nDistance: 9 40 200").unwrap();
+        assert_eq!(run.answers(), Ok(vec![(2, "71503".to_string())]));
+
+        assert_eq!(
+            run_part(&aoc_2023::YEAR, 6, 3, "").unwrap_err(),
+            "no solution registered for part 3"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(1_500)), "2µs");
+        assert_eq!(format_duration(Duration::from_micros(1_234)), "1.23ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50s");
+    }
+}
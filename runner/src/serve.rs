@@ -0,0 +1,413 @@
+//! A small HTTP API for running solutions from other tools.
+//!
+//! `POST /<year>/day/<day>` with the puzzle input as the body runs every
+//! part of the day, and `POST /<year>/day/<day>/part/<part>` just the one.
+//! The answers come back as JSON alongside timings, whatever normalising
+//! the input needed, and the lines parsing skipped. `?mode=strict` or
+//! `?mode=lenient` overrides the parse mode for one request.
+//!
+//! `GET /<year>` lists the days that have solutions.
+
+use crate::run::{self, DayRun};
+use aoc_2023::day4::{self, PastEnd};
+use aoc_common::{
+    http::{self, Request},
+    parsing::{self, Mode},
+    Year,
+};
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    process::ExitCode,
+    thread,
+    time::Duration,
+};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8023";
+
+/// Inputs are a few tens of kilobytes, so anything much bigger is a mistake.
+const MAX_BODY: usize = 16 << 20;
+
+/// How many connections are handled at once. The rest wait to be accepted.
+const WORKERS: usize = 8;
+
+/// How long a client gets to send its whole request, so a stalled or
+/// trickling one doesn't tie up a worker for good.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves `years` on `addr` until the process is killed.
+pub fn serve(years: &'static [Year], addr: &str) -> ExitCode {
+    let server = match Server::bind(addr, years) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("serve: couldn't listen on {addr}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match server.local_addr() {
+        Ok(addr) => println!("listening on http://{addr}"),
+        Err(err) => eprintln!("serve: {err}"),
+    }
+
+    server.run();
+    ExitCode::SUCCESS
+}
+
+pub struct Server {
+    listener: TcpListener,
+    years: &'static [Year],
+    mode: Mode,
+    past_end: PastEnd,
+}
+
+impl Server {
+    /// Requests without a `mode` are parsed in whatever mode this thread
+    /// is in now, so `--strict` and `AOC_PARSE` carry over, as do
+    /// `--past-end` and `AOC_PAST_END`.
+    pub fn bind(addr: &str, years: &'static [Year]) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            years,
+            mode: parsing::mode(),
+            past_end: day4::past_end(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Handles connections on a fixed pool of threads, so a slow day
+    /// doesn't hold up the rest and a flood of clients can't spawn threads
+    /// without limit.
+    pub fn run(self) {
+        thread::scope(|scope| {
+            for _ in 0..WORKERS {
+                scope.spawn(|| self.accept());
+            }
+        });
+    }
+
+    fn accept(&self) {
+        day4::set_past_end(self.past_end);
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => handle(stream, self.years, self.mode),
+                Err(err) => eprintln!("serve: {err}"),
+            }
+        }
+    }
+}
+
+fn handle(mut stream: TcpStream, years: &[Year], mode: Mode) {
+    let deadline = &mut http::Deadline::new(&stream, REQUEST_TIMEOUT);
+    let response = match http::read_request(deadline, MAX_BODY) {
+        Ok(request) => respond(&request, years, mode),
+        Err(err) => Response::error(err.status, err.reason),
+    };
+    let _ = stream.write_all(response.to_http().as_bytes());
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!("{{\"error\":{}}}", string(message)),
+        }
+    }
+
+    fn to_http(&self) -> String {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            501 => "Not Implemented",
+            _ => "Error",
+        };
+        format!(
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+fn respond(request: &Request, years: &[Year], mode: Mode) -> Response {
+    let (path, query) = request.path.split_once('?').unwrap_or((&request.path, ""));
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    let Some((year, rest)) = segments.split_first() else {
+        return Response::error(404, "expected /<year>/day/<day>");
+    };
+    let Some(year) = years.iter().find(|y| y.year.to_string() == *year) else {
+        return Response::error(404, &format!("no solutions registered for {year}"));
+    };
+
+    let (day, part) = match rest {
+        [] => {
+            return match request.method.as_str() {
+                "GET" => Response::ok(days_json(year)),
+                _ => Response::error(405, "use GET to list days"),
+            }
+        }
+        ["day", day] => (day, None),
+        ["day", day, "part", part] => match part.parse() {
+            Ok(part) => (day, Some(part)),
+            Err(_) => return Response::error(404, &format!("invalid part `{part}`")),
+        },
+        _ => return Response::error(404, "expected /<year>/day/<day>"),
+    };
+    let Ok(day) = day.parse::<u8>() else {
+        return Response::error(404, &format!("invalid day `{day}`"));
+    };
+    if request.method != "POST" {
+        return Response::error(405, "POST the puzzle input to run it");
+    }
+
+    let mode = match query_mode(query) {
+        Ok(requested) => requested.unwrap_or(mode),
+        Err(err) => return Response::error(400, &err),
+    };
+    parsing::set_mode(mode);
+
+    let run = match part {
+        Some(part) => run::run_part(year, day, part, &request.body),
+        None => run::run(year, day, &request.body),
+    };
+    match run {
+        Ok(run) => Response::ok(run_json(year, day, mode, &run)),
+        Err(err) => Response::error(404, &format!("day {day}: {err}")),
+    }
+}
+
+fn query_mode(query: &str) -> Result<Option<Mode>, String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("mode="))
+        .next_back()
+        .map(str::parse)
+        .transpose()
+}
+
+fn days_json(year: &Year) -> String {
+    let days = year
+        .days()
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{\"year\":{},\"days\":[{days}]}}", year.year)
+}
+
+fn run_json(year: &Year, day: u8, mode: Mode, run: &DayRun) -> String {
+    let parts = run
+        .parts
+        .iter()
+        .map(|part| {
+            let result = match &part.answer {
+                Ok(answer) => format!("\"answer\":{}", string(answer)),
+                Err(err) => format!("\"error\":{}", string(err)),
+            };
+            format!(
+                "{{\"part\":{},{result},\"elapsed_us\":{}}}",
+                part.part,
+                part.elapsed.as_micros()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let changes = run
+        .changes
+        .iter()
+        .map(|change| string(&change.to_string()))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let warnings = run
+        .warnings
+        .iter()
+        .map(|bad| {
+            format!(
+                "{{\"line\":{},\"text\":{},\"reason\":{}}}",
+                bad.line,
+                string(&bad.text),
+                string(&bad.reason)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mode = match mode {
+        Mode::Lenient => "lenient",
+        Mode::Strict => "strict",
+    };
+
+    format!(
+        "{{\"year\":{},\"day\":{day},\"mode\":\"{mode}\",\"parts\":[{parts}],\"elapsed_us\":{},\"changes\":[{changes}],\"warnings\":[{warnings}]}}",
+        year.year,
+        run.elapsed().as_micros()
+    )
+}
+
+/// `s` as a JSON string literal.
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Read;
+
+    const RACES: &str = "Time: 7 15 30\nDistance: 9 40 200";
+    const YEARS: &[Year] = &[aoc_2023::YEAR];
+
+    fn post(path: &str, body: &str) -> Response {
+        let request = Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            headers: Vec::new(),
+            body: body.to_string(),
+        };
+        respond(&request, YEARS, Mode::Lenient)
+    }
+
+    #[test]
+    fn test_run_a_day() {
+        let response = post("/2023/day/6", RACES);
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .starts_with("{\"year\":2023,\"day\":6,\"mode\":\"lenient\",\"parts\":[{\"part\":1,\"answer\":\"288\","));
+        assert!(response.body.contains("{\"part\":2,\"answer\":\"71503\","));
+        assert!(response
+            .body
+            .ends_with("\"changes\":[\"added a trailing newline\"],\"warnings\":[]}"));
+
+        let response = post("/2023/day/6/part/2", RACES);
+        assert!(response
+            .body
+            .contains("\"parts\":[{\"part\":2,\"answer\":\"71503\","));
+        assert!(!response.body.contains("\"part\":1"));
+    }
+
+    #[test]
+    fn test_parse_warnings_and_modes() {
+        let response = post("/2023/day/1/part/1", "1abc2\n\"nope\"\n");
+        assert!(response.body.contains("\"answer\":\"12\""));
+        assert!(response.body.contains(
+            "\"warnings\":[{\"line\":2,\"text\":\"\\\"nope\\\"\",\"reason\":\"no digits on line\"}]"
+        ));
+
+        let response = post("/2023/day/1/part/1?mode=strict", "1abc2\nnope\n");
+        assert!(response.body.contains("\"mode\":\"strict\""));
+        assert!(response
+            .body
+            .contains("\"error\":\"line 2: no digits on line: `nope`\""));
+        assert!(response.body.contains("\"warnings\":[]"));
+
+        assert_eq!(post("/2023/day/1?mode=loose", "").status, 400);
+    }
+
+    #[test]
+    fn test_bad_requests() {
+        assert_eq!(post("/2023/day/25", RACES).status, 404);
+        assert_eq!(post("/2023/day/6/part/3", RACES).status, 404);
+        assert_eq!(post("/2023/day/six", RACES).status, 404);
+        assert_eq!(post("/2022/day/6", RACES).status, 404);
+        assert_eq!(post("/", RACES).status, 404);
+        assert_eq!(
+            post("/2023/day/25", RACES).body,
+            "{\"error\":\"day 25: no solution registered\"}"
+        );
+
+        let get = Request {
+            method: "GET".to_string(),
+            path: "/2023/day/6".to_string(),
+            headers: Vec::new(),
+            body: String::new(),
+        };
+        assert_eq!(respond(&get, YEARS, Mode::Lenient).status, 405);
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(
+            string("a \"b\"\\\n\u{1}é"),
+            "\"a \\\"b\\\"\\\\\\n\\u0001é\""
+        );
+    }
+
+    #[test]
+    fn test_serve_on_localhost() {
+        let server = Server::bind("127.0.0.1:0", YEARS).unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let url = format!("http://{server_addr}");
+        thread::spawn(move || server.run());
+
+        let body = ureq::post(&format!("{url}/2023/day/6"))
+            .send_string(RACES)
+            .unwrap()
+            .into_string()
+            .unwrap();
+        assert!(body.contains("\"answer\":\"288\""));
+
+        let days = ureq::get(&format!("{url}/2023"))
+            .call()
+            .unwrap()
+            .into_string()
+            .unwrap();
+        assert_eq!(days, "{\"year\":2023,\"days\":[1,2,3,4,5,6]}");
+
+        match ureq::post(&format!("{url}/2023/day/25")).send_string("") {
+            Err(ureq::Error::Status(status, response)) => {
+                assert_eq!(status, 404);
+                assert!(response.into_string().unwrap().contains("no solution"));
+            }
+            other => panic!("expected a 404, got {other:?}"),
+        }
+
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+        stream
+            .write_all(b"POST /2023/day/6 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 501 Not Implemented\r\n"));
+    }
+}
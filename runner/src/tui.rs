@@ -0,0 +1,447 @@
+use crate::run::{self, format_duration, DayRun};
+use aoc_common::{
+    input::InputCache,
+    submit::{History, Verdict},
+    Year,
+};
+use ratatui::{
+    crossterm::{
+        event::{self, Event, KeyCode, KeyEventKind},
+        execute,
+        terminal::{enable_raw_mode, EnterAlternateScreen},
+    },
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+use std::{
+    cell::Cell, collections::BTreeMap, env, fs, io, panic, process::Command, process::ExitCode,
+};
+
+const HINTS: &str = "↑↓ day · r run · a run all · i input · PgUp/PgDn scroll · e edit · q quit";
+
+thread_local! {
+    static RUNNING_DAY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs the terminal UI until the user quits.
+pub fn run(year: &Year) -> ExitCode {
+    // A panicking part shows up in the results, so keep the default hook
+    // from printing over the screen while a day runs. This goes in before
+    // ratatui's hook, which restores the terminal first, so a panic in the
+    // UI itself is still reported.
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !RUNNING_DAY.get() {
+            default(info);
+        }
+    }));
+
+    let mut app = App::new(year, InputCache::from_env(year));
+    let mut terminal = ratatui::init();
+
+    let result = (|| -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| app.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match app.handle(key.code) {
+                Action::Continue => {}
+                Action::Quit => return Ok(()),
+                Action::Edit(day) => {
+                    ratatui::restore();
+                    let edited = edit(&app.cache, day);
+                    resume(&mut terminal)?;
+                    app.edited(day, edited);
+                }
+            }
+        }
+    })();
+
+    ratatui::restore();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("tui: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Takes the terminal back after [`ratatui::restore`]. Calling
+/// [`ratatui::init`] again would do the same, but would also wrap the panic
+/// hook once more for every edit.
+fn resume(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()
+}
+
+/// Opens the day's input in `$VISUAL` or `$EDITOR`, creating it if needed.
+fn edit(cache: &InputCache, day: u8) -> io::Result<()> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    fs::create_dir_all(cache.dir())?;
+
+    let status = Command::new(&editor).arg(cache.path(day)).status()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(io::Error::other(format!("{editor} exited with {status}"))),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Results,
+    Input,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Continue,
+    Edit(u8),
+    Quit,
+}
+
+struct App<'y> {
+    year: &'y Year,
+    cache: InputCache,
+    history: Option<History>,
+    days: Vec<u8>,
+    runs: BTreeMap<u8, Result<DayRun, String>>,
+    list: ListState,
+    view: View,
+    scroll: u16,
+    message: String,
+}
+
+impl<'y> App<'y> {
+    fn new(year: &'y Year, cache: InputCache) -> Self {
+        Self {
+            year,
+            history: History::for_cache(&cache).ok(),
+            cache,
+            days: year.days(),
+            runs: BTreeMap::new(),
+            list: ListState::default().with_selected(Some(0)),
+            view: View::Results,
+            scroll: 0,
+            message: String::new(),
+        }
+    }
+
+    fn selected(&self) -> Option<u8> {
+        self.days.get(self.list.selected()?).copied()
+    }
+
+    fn handle(&mut self, key: KeyCode) -> Action {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.select(1),
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(day) = self.selected() {
+                    self.run_day(day);
+                }
+            }
+            KeyCode::Char('a') => {
+                for day in self.days.clone() {
+                    self.run_day(day);
+                }
+                self.message = format!("ran {} days", self.days.len());
+            }
+            KeyCode::Tab | KeyCode::Char('i') => {
+                self.view = match self.view {
+                    View::Results => View::Input,
+                    View::Input => View::Results,
+                };
+                self.scroll = 0;
+            }
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('e') => {
+                if let Some(day) = self.selected() {
+                    return Action::Edit(day);
+                }
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn select(&mut self, step: isize) {
+        if self.days.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0) as isize;
+        let next = (current + step).rem_euclid(self.days.len() as isize);
+        self.list.select(Some(next as usize));
+        self.scroll = 0;
+    }
+
+    fn run_day(&mut self, day: u8) {
+        RUNNING_DAY.set(true);
+        let result = self
+            .cache
+            .get(day)
+            .map_err(|err| err.to_string())
+            .and_then(|input| run::run(self.year, day, &input));
+        RUNNING_DAY.set(false);
+
+        // Pick up anything submitted since the UI started.
+        self.history = History::for_cache(&self.cache).ok();
+        self.message = match &result {
+            Ok(run) => format!("ran day {day} in {}", format_duration(run.elapsed())),
+            Err(err) => format!("day {day}: {err}"),
+        };
+        self.runs.insert(day, result);
+    }
+
+    fn edited(&mut self, day: u8, edited: io::Result<()>) {
+        match edited {
+            Ok(()) => self.run_day(day),
+            Err(err) => self.message = format!("day {day}: {err}"),
+        }
+    }
+
+    fn verdict(&self, day: u8, part: u8, answer: &str) -> Option<Verdict> {
+        self.history
+            .as_ref()?
+            .verdict(self.year.year, day, part, answer)
+    }
+
+    /// A one character summary of the day: not run, failed, every answer
+    /// accepted, some answer rejected, or not yet checked.
+    fn status(&self, day: u8) -> Span<'static> {
+        let Some(result) = self.runs.get(&day) else {
+            return "·".dark_gray();
+        };
+        let Ok(answers) = result
+            .as_ref()
+            .map_err(|_| ())
+            .and_then(|run| run.answers().map_err(|_| ()))
+        else {
+            return "!".red();
+        };
+
+        let verdicts = answers
+            .iter()
+            .map(|(part, answer)| self.verdict(day, *part, answer))
+            .collect::<Vec<_>>();
+
+        if verdicts.iter().all(|v| v == &Some(Verdict::Correct)) {
+            "✓".green()
+        } else if verdicts
+            .iter()
+            .any(|v| v.is_some() && v != &Some(Verdict::Correct))
+        {
+            "✗".red()
+        } else {
+            "?".yellow()
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [days, detail] =
+            Layout::horizontal([Constraint::Length(24), Constraint::Min(0)]).areas(body);
+
+        frame.render_widget(
+            Line::from(vec![
+                format!(" Advent of Code {} ", self.year.year).bold(),
+                HINTS.dark_gray(),
+            ]),
+            header,
+        );
+
+        let items = self
+            .days
+            .iter()
+            .map(|&day| {
+                let elapsed = match self.runs.get(&day) {
+                    Some(Ok(run)) => format_duration(run.elapsed()),
+                    _ => String::new(),
+                };
+                ListItem::new(Line::from(vec![
+                    self.status(day),
+                    format!(" Day {day:>2}  {elapsed:>9}").into(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Days "))
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(list, days, &mut self.list);
+
+        let (title, text) = match (self.selected(), self.view) {
+            (None, _) => (" Nothing registered ".to_string(), Text::default()),
+            (Some(day), View::Results) => (format!(" Day {day} "), self.results(day)),
+            (Some(day), View::Input) => (format!(" Day {day} input "), self.input(day)),
+        };
+        frame.render_widget(
+            Paragraph::new(text)
+                .block(Block::bordered().title(title))
+                .scroll((self.scroll, 0)),
+            detail,
+        );
+
+        frame.render_widget(Line::from(format!(" {}", self.message)), footer);
+    }
+
+    fn results(&self, day: u8) -> Text<'static> {
+        let run = match self.runs.get(&day) {
+            None => return Text::from("Not run yet, press r to run it.".dark_gray()),
+            Some(Err(err)) => return Text::from(err.clone().red()),
+            Some(Ok(run)) => run,
+        };
+
+        let mut lines = Vec::new();
+        for part in &run.parts {
+            let (answer, label) = match &part.answer {
+                Ok(answer) => (
+                    answer.clone().bold(),
+                    verdict_label(self.verdict(day, part.part, answer)),
+                ),
+                Err(err) => (err.clone().red(), "failed".red()),
+            };
+            lines.push(Line::from(vec![
+                format!("Part {}  ", part.part).into(),
+                answer,
+                "  ".into(),
+                label,
+                format!("  {}", format_duration(part.elapsed)).dark_gray(),
+            ]));
+        }
+
+        lines.push(Line::default());
+        for change in &run.changes {
+            lines.push(Line::from(format!("note: input {change}").dark_gray()));
+        }
+        match run.warnings.len() {
+            0 => lines.push(Line::from("No parse warnings".dark_gray())),
+            n => {
+                let lines_skipped = match n {
+                    1 => "1 line skipped".to_string(),
+                    n => format!("{n} lines skipped"),
+                };
+                lines.push(Line::from(
+                    format!("{lines_skipped} while parsing:").yellow(),
+                ));
+                for skipped in &run.warnings {
+                    lines.push(Line::from(format!("  {skipped}")));
+                }
+            }
+        }
+
+        Text::from(lines)
+    }
+
+    fn input(&self, day: u8) -> Text<'static> {
+        match fs::read_to_string(self.cache.path(day)) {
+            Ok(input) => Text::from(input),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Text::from("No input yet, press r to download it or e to write it.".dark_gray())
+            }
+            Err(err) => Text::from(err.to_string().red()),
+        }
+    }
+}
+
+fn verdict_label(verdict: Option<Verdict>) -> Span<'static> {
+    match verdict {
+        Some(Verdict::Correct) => "correct".green(),
+        Some(Verdict::TooHigh) => "too high".red(),
+        Some(Verdict::TooLow) => "too low".red(),
+        Some(_) => "wrong".red(),
+        None => "unverified".yellow(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::path::PathBuf;
+
+    fn temp_cache(name: &str) -> (PathBuf, InputCache) {
+        let dir = env::temp_dir().join(format!("aoc-tui-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let cache = InputCache::new(2023, &dir);
+        (dir, cache)
+    }
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_select_and_run_a_day() {
+        let (dir, cache) = temp_cache("run");
+        fs::write(cache.path(6), "Time: 7 15 30\nDistance: 9 40 200\n").unwrap();
+        let mut app = App::new(&aoc_2023::YEAR, cache);
+
+        assert_eq!(app.selected(), Some(1));
+        assert_eq!(app.handle(KeyCode::Up), Action::Continue);
+        assert_eq!(app.selected(), Some(6));
+
+        app.handle(KeyCode::Char('r'));
+        let answers = app.runs[&6].as_ref().unwrap().answers().unwrap();
+        assert_eq!(answers, [(1, "288".to_string()), (2, "71503".to_string())]);
+        assert_eq!(app.status(6).content, "?");
+        assert_eq!(app.status(1).content, "·");
+
+        let screen = screen(&mut app);
+        assert!(screen.contains("Part 1  288  unverified"));
+        assert!(screen.contains("No parse warnings"));
+
+        app.handle(KeyCode::Char('i'));
+        assert!(self::screen(&mut app).contains("Distance: 9 40 200"));
+
+        assert_eq!(app.handle(KeyCode::Char('e')), Action::Edit(6));
+        assert_eq!(app.handle(KeyCode::Char('q')), Action::Quit);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_failures_and_warnings_are_shown() {
+        let (dir, cache) = temp_cache("warnings");
+        fs::write(cache.path(1), "1abc2\nnothing here\n").unwrap();
+        let mut app = App::new(&aoc_2023::YEAR, cache);
+
+        app.handle(KeyCode::Enter);
+        let screen = screen(&mut app);
+        assert!(screen.contains("1 line skipped while parsing:"));
+        assert!(screen.contains("line 2: no digits on line: `nothing here`"));
+
+        app.handle(KeyCode::Down);
+        app.handle(KeyCode::Enter);
+        // No input for day 2 and nowhere to download it from.
+        assert_eq!(app.status(2).content, "!");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
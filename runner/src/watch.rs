@@ -0,0 +1,297 @@
+use crate::run::{self, format_duration};
+use aoc_common::{input::InputCache, Year};
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// Re-runs `day` on its input and examples whenever one of them changes,
+/// along with the day's own tests, until interrupted.
+///
+/// Examples live next to the input as `dayN-example.txt`, or
+/// `dayN-example-2.txt` and so on when there's more than one.
+pub fn watch(year: &Year, day: u8) -> ExitCode {
+    if year.solvers(day).is_empty() {
+        eprintln!("{} day {day}: no solution registered", year.year);
+        return ExitCode::FAILURE;
+    }
+
+    let cache = InputCache::from_env(year);
+    if let Err(err) = cache.get(day) {
+        eprintln!("day {day}: {err}, watching the examples only");
+    }
+    println!(
+        "watching day {day} in {}, ctrl-c to stop",
+        cache.dir().display()
+    );
+
+    let mut seen = Stamps::new();
+    let mut previous = Snapshot::new();
+    loop {
+        let files = watched(&cache, day);
+        let current = stamps(&files);
+
+        if current != seen {
+            let changed = changed(&seen, &current);
+            println!("\n-- changed: {}", changed.join(", "));
+
+            let snapshot = snapshot(year, day, &files);
+            for diff in diff(&previous, &snapshot) {
+                println!("{diff}");
+            }
+            sample_tests(year, day);
+
+            seen = current;
+            previous = snapshot;
+        }
+
+        thread::sleep(POLL);
+    }
+}
+
+/// The day's input and examples that exist right now, examples first.
+fn watched(cache: &InputCache, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("day{day}-example");
+    let mut files = fs::read_dir(cache.dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = file_name(path);
+            name.starts_with(&prefix) && name.ends_with(".txt")
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let input = cache.path(day);
+    if input.exists() {
+        files.push(input);
+    }
+    files
+}
+
+type Stamps = BTreeMap<PathBuf, Option<SystemTime>>;
+
+fn stamps(files: &[PathBuf]) -> Stamps {
+    files
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Names of the files that appeared, disappeared or were touched.
+fn changed(before: &Stamps, after: &Stamps) -> Vec<String> {
+    let mut paths = before
+        .keys()
+        .chain(after.keys())
+        .filter(|path| before.get(*path) != after.get(*path))
+        .map(|path| file_name(path))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+type Answer = Result<String, String>;
+
+/// Every part's answer for every watched file, keyed by file name.
+type Snapshot = BTreeMap<(String, u8), Answer>;
+
+fn snapshot(year: &Year, day: u8, files: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    for path in files {
+        let name = file_name(path);
+        // A file can go away between listing and reading it, in which case
+        // the next poll notices.
+        let Ok(input) = fs::read_to_string(path) else {
+            continue;
+        };
+        let run = match run::run(year, day, &input) {
+            Ok(run) => run,
+            Err(err) => {
+                eprintln!("{name}: {err}");
+                continue;
+            }
+        };
+
+        for skipped in &run.warnings {
+            eprintln!("warning: {name}: skipped {skipped}");
+        }
+        for part in run.parts {
+            println!(
+                "   {name} part {} took {}",
+                part.part,
+                format_duration(part.elapsed)
+            );
+            snapshot.insert((name.clone(), part.part), part.answer);
+        }
+    }
+
+    snapshot
+}
+
+/// How one part's answer on one file moved between two runs.
+#[derive(Debug, PartialEq, Eq)]
+struct Diff {
+    file: String,
+    part: u8,
+    before: Option<Answer>,
+    after: Option<Answer>,
+}
+
+fn diff(before: &Snapshot, after: &Snapshot) -> Vec<Diff> {
+    let mut keys = before.keys().chain(after.keys()).collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|key| Diff {
+            file: key.0.clone(),
+            part: key.1,
+            before: before.get(key).cloned(),
+            after: after.get(key).cloned(),
+        })
+        .collect()
+}
+
+fn show(answer: &Answer) -> String {
+    match answer {
+        Ok(answer) => answer.clone(),
+        Err(err) => format!("error ({err})"),
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match (&self.before, &self.after) {
+            (Some(before), Some(after)) if before == after => ' ',
+            (None, _) => '+',
+            (_, None) => '-',
+            _ => '~',
+        };
+        write!(f, "{marker}  {} part {}: ", self.file, self.part)?;
+
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) if before == after => write!(f, "{}", show(after)),
+            (Some(before), Some(after)) => write!(f, "{} -> {}", show(before), show(after)),
+            (None, Some(after)) => write!(f, "{}", show(after)),
+            (Some(before), None) => write!(f, "{} -> gone", show(before)),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Runs the tests in the day's module, which is where its samples are
+/// checked, and reports whether they passed.
+fn sample_tests(year: &Year, day: u8) {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .arg("test")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(year.manifest_path())
+        .arg("--lib")
+        .arg(format!("day{day}::"))
+        .status();
+
+    match status {
+        Ok(status) if status.success() => println!("   sample tests passed"),
+        Ok(status) => println!("   sample tests failed ({status})"),
+        Err(err) => eprintln!("   couldn't run the sample tests: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_watched_files_and_changes() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let cache = InputCache::new(2023, &dir);
+
+        assert!(watched(&cache, 6).is_empty());
+
+        for name in [
+            "day6.txt",
+            "day6-example.txt",
+            "day6-example-2.txt",
+            "day16.txt",
+        ] {
+            fs::write(dir.join(name), "Time: 7 15 30\nDistance: 9 40 200\n").unwrap();
+        }
+        let files = watched(&cache, 6);
+        let names = files.iter().map(|path| file_name(path)).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["day6-example-2.txt", "day6-example.txt", "day6.txt"]
+        );
+
+        let before = stamps(&files);
+        assert_eq!(changed(&Stamps::new(), &before).len(), 3);
+        assert!(changed(&before, &stamps(&files)).is_empty());
+
+        fs::remove_file(dir.join("day6-example-2.txt")).unwrap();
+        assert_eq!(
+            changed(&before, &stamps(&watched(&cache, 6))),
+            ["day6-example-2.txt"]
+        );
+
+        let snapshot = snapshot(&aoc_2023::YEAR, 6, &files);
+        assert_eq!(snapshot.len(), 4);
+        assert_eq!(
+            snapshot[&("day6.txt".to_string(), 1)],
+            Ok("288".to_string())
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff() {
+        let key = |file: &str, part| (file.to_string(), part);
+        let before = Snapshot::from([
+            (key("day5.txt", 1), Ok("35".to_string())),
+            (key("day5.txt", 2), Ok("46".to_string())),
+            (key("day5-example.txt", 1), Ok("35".to_string())),
+        ]);
+        let after = Snapshot::from([
+            (key("day5.txt", 1), Ok("35".to_string())),
+            (key("day5.txt", 2), Err("failed to parse seeds".to_string())),
+            (key("day5-example-2.txt", 1), Ok("7".to_string())),
+        ]);
+
+        let lines = diff(&before, &after)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "+  day5-example-2.txt part 1: 7",
+                "-  day5-example.txt part 1: 35 -> gone",
+                "   day5.txt part 1: 35",
+                "~  day5.txt part 2: 46 -> error (failed to parse seeds)",
+            ]
+        );
+    }
+}